@@ -0,0 +1,1158 @@
+//! Emission math of the faucet.
+//!
+//! Pure functions of the faucet state and the current time: no accounts, no sysvars and
+//! no Anchor types, so the same calculation runs in drip, in preview_drip, in off-chain
+//! clients and under `cargo test`. The program converts its accounts and errors in lib.rs.
+
+use std::convert::TryFrom;
+
+/// Basis points of a whole amount.
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Clock counting the blocks of a faucet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClockSource {
+    /// Blocks are counted in seconds of the unix timestamp.
+    UnixTimestamp,
+    /// Blocks are counted in slots.
+    Slot,
+}
+
+/// A segment of the emission schedule.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Segment {
+    /// Block height at which the segment starts.
+    pub start_block_height: u64,
+    /// Tokens generated by each block of the segment.
+    pub reward_per_block: u64,
+}
+
+/// Why the emission math fails.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EmissionError {
+    /// The supply has reached the max supply.
+    TotalSupplyLimit,
+    /// The faucet has accrued its last tokens.
+    FaucetFinished,
+    /// Drip is paused.
+    Paused,
+    /// Emission has not started yet.
+    EmissionNotStarted,
+    /// Emission has ended.
+    EmissionEnded,
+    /// The current time is not after the last block generation.
+    InvalidTimestamp,
+    /// Not a whole block has passed since the last block generation.
+    InsufficientInterval,
+    /// The emission schedule does not cover the block height.
+    InvalidEmissionSchedule,
+    /// An amount or a time overflows.
+    ArithmeticOverflow,
+    /// A timestamp or a slot is out of range.
+    TimestampConversion,
+    /// A block generation rate or a total weight is zero.
+    DivisionByZero,
+}
+
+/// State of a faucet read by [compute_drip].
+#[derive(Clone, Debug)]
+pub struct EmissionState {
+    /// Emission schedule, sorted by start block height and starting at height 0.
+    pub segments: Vec<Segment>,
+    /// Distribution weight of each bucket.
+    pub weights: Vec<u64>,
+    /// Rounding dust of each bucket not yet distributed, in units of 1 / total weight lamport.
+    pub remainders: Vec<u64>,
+    /// Supply of the mint, including the accruals not yet claimed.
+    pub supply: u64,
+    /// Max supply of the mint, the cap of emission.
+    pub max_supply: u64,
+    /// Whether the max supply has been reached and emission is over.
+    pub finished: bool,
+    /// Whether drip is paused.
+    pub paused: bool,
+    /// Clock source counting the blocks.
+    pub clock_source: ClockSource,
+    /// Seconds or slots, depending on [clock_source], between two generated blocks.
+    pub block_gen_rate: u32,
+    /// Max number of blocks generated by a single drip.
+    pub max_blocks_per_drip: u64,
+    /// Whether the pending blocks older than [grace_blocks] are forfeited instead of caught up.
+    pub forfeit_missed_blocks: bool,
+    /// Number of the latest pending blocks kept in forfeit mode.
+    pub grace_blocks: u64,
+    /// Share of each drip rewarded to its caller, in basis points.
+    pub cranker_reward_bps: u16,
+    /// Max reward of the caller of a single drip.
+    pub max_cranker_reward: u64,
+    /// The current block height.
+    pub current_block_height: u64,
+    /// The timestamp of the last block generation.
+    pub last_gen_block_timestamp: i64,
+    /// The slot of the last block generation.
+    pub last_gen_block_slot: u64,
+    /// When emission starts, with the unix timestamp clock source.
+    pub start_timestamp: i64,
+    /// When emission ends, or 0 if it never ends, with the unix timestamp clock source.
+    pub end_timestamp: i64,
+    /// Slot at which emission starts, with the slot clock source.
+    pub start_slot: u64,
+    /// Slot at which emission ends, or 0 if it never ends, with the slot clock source.
+    pub end_slot: u64,
+}
+
+/// The current time, in both clocks.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Now {
+    /// The current unix timestamp.
+    pub unix_timestamp: i64,
+    /// The current slot.
+    pub slot: u64,
+}
+
+/// What a drip does.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DripPlan {
+    /// interval since the last block generation, in seconds or slots depending on the clock source.
+    pub intervals: i64,
+    /// Number of blocks forfeited.
+    pub forfeited_block_num: u64,
+    /// Amount of token forfeited.
+    pub forfeited_amount: u64,
+    /// Number of blocks generated.
+    pub gen_block_num: u64,
+    /// Number of blocks pending but not generated, left to the next drips.
+    pub backlog_block_num: u64,
+    /// The block height after the drip.
+    pub current_block_height: u64,
+    /// The era of the next block.
+    pub current_era: u8,
+    /// The reward of the next block.
+    pub current_reward: u64,
+    /// The timestamp of last block generation after the drip.
+    pub last_gen_block_timestamp: i64,
+    /// The slot of last block generation after the drip.
+    pub last_gen_block_slot: u64,
+    /// Whether the drip reaches the max supply and ends emission.
+    pub cap_reached: bool,
+    /// Amount of token minted to the caller.
+    pub cranker_reward: u64,
+    /// Amount of token accrued to each bucket.
+    pub amounts: Vec<u64>,
+    /// Rounding dust of each bucket after the drip.
+    pub remainders: Vec<u64>,
+    /// Supply after the drip, including the accruals not yet claimed.
+    pub supply: u64,
+}
+
+/// Calculate what a drip of the faucet in `state` does at `now`.
+pub fn compute_drip(state: &EmissionState, now: Now) -> Result<DripPlan, EmissionError> {
+    // Check if token supply exceeds.
+    if state.supply >= state.max_supply {
+        return Err(EmissionError::TotalSupplyLimit);
+    }
+
+    // Check whether the faucet has already accrued its last tokens or is paused.
+    if state.finished {
+        return Err(EmissionError::FaucetFinished);
+    }
+    if state.paused {
+        return Err(EmissionError::Paused);
+    }
+
+    // Blocks are counted in seconds or in slots, depending on the clock source.
+    let (current, start, end, mut last_gen_block_time) = match state.clock_source {
+        ClockSource::UnixTimestamp => (
+            now.unix_timestamp,
+            state.start_timestamp,
+            state.end_timestamp,
+            state.last_gen_block_timestamp,
+        ),
+        ClockSource::Slot => (
+            to_time(now.slot)?,
+            to_time(state.start_slot)?,
+            to_time(state.end_slot)?,
+            to_time(state.last_gen_block_slot)?,
+        ),
+    };
+    let block_gen_rate = u64::from(state.block_gen_rate);
+
+    // Check emission has started and has not ended.
+    // No block is generated past the end, so the time is clamped to it.
+    if current < start {
+        return Err(EmissionError::EmissionNotStarted);
+    }
+    let drip_time = if end > 0 {
+        if last_gen_block_time
+            .checked_add(i64::from(state.block_gen_rate))
+            .ok_or(EmissionError::ArithmeticOverflow)?
+            > end
+        {
+            return Err(EmissionError::EmissionEnded);
+        }
+        current.min(end)
+    } else {
+        current
+    };
+
+    // Check current timestamp validity.
+    if drip_time <= last_gen_block_time {
+        return Err(EmissionError::InvalidTimestamp);
+    }
+
+    // Check call interval.
+    let intervals = drip_time
+        .checked_sub(last_gen_block_time)
+        .ok_or(EmissionError::ArithmeticOverflow)?;
+    if intervals < i64::from(state.block_gen_rate) {
+        return Err(EmissionError::InsufficientInterval);
+    }
+
+    // Calculate the number of blocks pending since the last block generation.
+    let mut pending_block_num = u64::try_from(intervals)
+        .map_err(|_| EmissionError::TimestampConversion)?
+        .checked_div(block_gen_rate)
+        .ok_or(EmissionError::DivisionByZero)?;
+
+    // In forfeit mode, only the latest [grace_blocks] pending blocks are kept;
+    // the older ones are discarded and their tokens are never minted.
+    let mut block_height = state.current_block_height;
+    let mut forfeited_block_num = 0;
+    let mut forfeited_amount = 0;
+    if state.forfeit_missed_blocks && pending_block_num > state.grace_blocks {
+        forfeited_block_num = pending_block_num
+            .checked_sub(state.grace_blocks)
+            .ok_or(EmissionError::ArithmeticOverflow)?;
+        forfeited_amount = block_rewards(&state.segments, block_height, forfeited_block_num)?;
+        pending_block_num = state.grace_blocks;
+
+        block_height = block_height
+            .checked_add(forfeited_block_num)
+            .ok_or(EmissionError::ArithmeticOverflow)?;
+        last_gen_block_time = advance(last_gen_block_time, forfeited_block_num, block_gen_rate)?;
+    }
+
+    // Calculate the number of blocks that should be generated.
+    // At most [max_blocks_per_drip] are generated at once, the backlog is left to the next drips.
+    let gen_block_num = pending_block_num.min(state.max_blocks_per_drip);
+    let backlog_block_num = pending_block_num
+        .checked_sub(gen_block_num)
+        .ok_or(EmissionError::ArithmeticOverflow)?;
+
+    // Calculate the numbers of tokens that should be minted.
+    // The blocks may straddle one or more segments of the emission schedule,
+    // so every era is rewarded separately.
+    let distribution_amounts = block_rewards(&state.segments, block_height, gen_block_num)?;
+
+    // The dust carried over by the previous drips is owed to the recipients as well,
+    // so it counts against the remaining headroom below the max supply.
+    let headroom = state
+        .max_supply
+        .checked_sub(state.supply)
+        .ok_or(EmissionError::ArithmeticOverflow)?;
+    let owed_dust = owed_dust(&state.weights, &state.remainders)?;
+    let cap_reached = distribution_amounts
+        .checked_add(owed_dust)
+        .ok_or(EmissionError::ArithmeticOverflow)?
+        >= headroom;
+
+    // Calculate the number of tokens of this drip.
+    // The last drip is scaled down so that the supply ends exactly at the cap;
+    // other faucets of the mint may have left less headroom than the dust owed.
+    let drip_amount = if cap_reached {
+        headroom.saturating_sub(owed_dust)
+    } else {
+        distribution_amounts
+    };
+
+    // The cranker takes its share of the drip first.
+    let cranker_reward = cranker_reward(
+        drip_amount,
+        state.cranker_reward_bps,
+        state.max_cranker_reward,
+    )?;
+    let bucket_amount = drip_amount
+        .checked_sub(cranker_reward)
+        .ok_or(EmissionError::ArithmeticOverflow)?;
+
+    // Calculate the number of tokens that should be distributed to each recipient.
+    // The dust left over by the previous drips is carried into this one.
+    let mut remainders = state.remainders.clone();
+    let amounts = if cap_reached {
+        split_final_by_weight(
+            bucket_amount,
+            &state.weights,
+            &mut remainders,
+            headroom
+                .checked_sub(cranker_reward)
+                .ok_or(EmissionError::ArithmeticOverflow)?,
+        )?
+    } else {
+        split_by_weight(bucket_amount, &state.weights, &mut remainders)?
+    };
+
+    // Block height, era and block reward of the next block.
+    let current_block_height = block_height
+        .checked_add(gen_block_num)
+        .ok_or(EmissionError::ArithmeticOverflow)?;
+    let (current_era, current_reward) = era_and_reward(&state.segments, current_block_height)?;
+
+    // The time of the latest block generation moves only by the blocks actually generated.
+    // The other clock only records when the drip took place.
+    last_gen_block_time = advance(last_gen_block_time, gen_block_num, block_gen_rate)?;
+    let (last_gen_block_timestamp, last_gen_block_slot) = match state.clock_source {
+        ClockSource::UnixTimestamp => (last_gen_block_time, now.slot),
+        ClockSource::Slot => (
+            now.unix_timestamp,
+            u64::try_from(last_gen_block_time).map_err(|_| EmissionError::TimestampConversion)?,
+        ),
+    };
+
+    let supply = amounts
+        .iter()
+        .try_fold(state.supply, |sum, amount| sum.checked_add(*amount))
+        .and_then(|sum| sum.checked_add(cranker_reward))
+        .ok_or(EmissionError::ArithmeticOverflow)?;
+
+    Ok(DripPlan {
+        intervals: intervals,
+        forfeited_block_num: forfeited_block_num,
+        forfeited_amount: forfeited_amount,
+        gen_block_num: gen_block_num,
+        backlog_block_num: backlog_block_num,
+        current_block_height: current_block_height,
+        current_era: current_era,
+        current_reward: current_reward,
+        last_gen_block_timestamp: last_gen_block_timestamp,
+        last_gen_block_slot: last_gen_block_slot,
+        cap_reached: cap_reached,
+        cranker_reward: cranker_reward,
+        amounts: amounts,
+        remainders: remainders,
+        supply: supply,
+    })
+}
+
+/// A slot as a time of the slot clock.
+fn to_time(slot: u64) -> Result<i64, EmissionError> {
+    i64::try_from(slot).map_err(|_| EmissionError::TimestampConversion)
+}
+
+/// Time forfeited by a pause from `paused_at` to `now`: only the part of it during
+/// emission, between `start` and `end` (or 0 if it never ends), so that the blocks
+/// pending before the pause are kept.
+pub fn forfeited_pause_time(
+    paused_at: i64,
+    now: i64,
+    start: i64,
+    end: i64,
+) -> Result<i64, EmissionError> {
+    let until = if end > 0 { now.min(end) } else { now };
+
+    Ok(until
+        .checked_sub(paused_at.max(start))
+        .ok_or(EmissionError::ArithmeticOverflow)?
+        .max(0))
+}
+
+/// Slots forfeited by a pause, like [forfeited_pause_time] with the slot clock.
+pub fn forfeited_pause_slots(
+    paused_at: u64,
+    now: u64,
+    start: u64,
+    end: u64,
+) -> Result<u64, EmissionError> {
+    u64::try_from(forfeited_pause_time(
+        to_time(paused_at)?,
+        to_time(now)?,
+        to_time(start)?,
+        to_time(end)?,
+    )?)
+    .map_err(|_| EmissionError::TimestampConversion)
+}
+
+/// The time `block_num` blocks of `block_gen_rate` after `time`.
+fn advance(time: i64, block_num: u64, block_gen_rate: u64) -> Result<i64, EmissionError> {
+    let elapsed = block_num
+        .checked_mul(block_gen_rate)
+        .ok_or(EmissionError::ArithmeticOverflow)?;
+
+    time.checked_add(i64::try_from(elapsed).map_err(|_| EmissionError::TimestampConversion)?)
+        .ok_or(EmissionError::ArithmeticOverflow)
+}
+
+/// Era and reward of the block at `block_height`.
+pub fn era_and_reward(segments: &[Segment], block_height: u64) -> Result<(u8, u64), EmissionError> {
+    let era = segments
+        .iter()
+        .rposition(|segment| segment.start_block_height <= block_height)
+        .ok_or(EmissionError::InvalidEmissionSchedule)?;
+
+    Ok((
+        u8::try_from(era).map_err(|_| EmissionError::InvalidEmissionSchedule)?,
+        segments[era].reward_per_block,
+    ))
+}
+
+/// Total reward of `block_num` blocks starting at `block_height`,
+/// splitting the blocks at every segment boundary they straddle.
+pub fn block_rewards(
+    segments: &[Segment],
+    block_height: u64,
+    block_num: u64,
+) -> Result<u64, EmissionError> {
+    let end_height = block_height
+        .checked_add(block_num)
+        .ok_or(EmissionError::ArithmeticOverflow)?;
+
+    let mut rewards: u64 = 0;
+    let mut height = block_height;
+    while height < end_height {
+        let (era, reward) = era_and_reward(segments, height)?;
+
+        // Reward the blocks up to the start of the next segment, or the last block.
+        let era_end_height = match segments.get(era as usize + 1) {
+            Some(next) => end_height.min(next.start_block_height),
+            None => end_height,
+        };
+        let blocks = era_end_height
+            .checked_sub(height)
+            .ok_or(EmissionError::InvalidEmissionSchedule)?;
+        rewards = blocks
+            .checked_mul(reward)
+            .and_then(|era_rewards| rewards.checked_add(era_rewards))
+            .ok_or(EmissionError::ArithmeticOverflow)?;
+        height = era_end_height;
+    }
+
+    Ok(rewards)
+}
+
+/// Share of `amount` rewarded to the cranker: `bps` basis points of it, at most `max_reward`.
+pub fn cranker_reward(amount: u64, bps: u16, max_reward: u64) -> Result<u64, EmissionError> {
+    let reward = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(EmissionError::ArithmeticOverflow)?
+        .checked_div(MAX_BASIS_POINTS as u128)
+        .ok_or(EmissionError::DivisionByZero)?;
+
+    Ok(u64::try_from(reward)
+        .map_err(|_| EmissionError::ArithmeticOverflow)?
+        .min(max_reward))
+}
+
+/// Total distribution weight.
+fn total_weight(weights: &[u64]) -> Result<u64, EmissionError> {
+    weights
+        .iter()
+        .try_fold(0u64, |sum, weight| sum.checked_add(*weight))
+        .ok_or(EmissionError::ArithmeticOverflow)
+}
+
+/// Whole lamports of rounding dust carried over in `remainders` and not yet distributed.
+pub fn owed_dust(weights: &[u64], remainders: &[u64]) -> Result<u64, EmissionError> {
+    remainders
+        .iter()
+        .try_fold(0u64, |sum, remainder| sum.checked_add(*remainder))
+        .ok_or(EmissionError::ArithmeticOverflow)?
+        .checked_div(total_weight(weights)?)
+        .ok_or(EmissionError::DivisionByZero)
+}
+
+/// Split `amount` between the buckets in proportion to their `weights`.
+/// Every share is rounded down; the fraction of a lamport a bucket could not
+/// receive is kept in its remainder (in units of 1 / total weight) and added to its
+/// share of the next split, so over the lifetime of the faucet every bucket
+/// receives exactly its share of all emitted tokens.
+pub fn split_by_weight(
+    amount: u64,
+    weights: &[u64],
+    remainders: &mut [u64],
+) -> Result<Vec<u64>, EmissionError> {
+    let total_weight = total_weight(weights)? as u128;
+
+    let mut shares = Vec::with_capacity(weights.len());
+    for (weight, remainder) in weights.iter().zip(remainders.iter_mut()) {
+        let numerator = (amount as u128)
+            .checked_mul(*weight as u128)
+            .and_then(|product| product.checked_add(*remainder as u128))
+            .ok_or(EmissionError::ArithmeticOverflow)?;
+        let share = numerator
+            .checked_div(total_weight)
+            .ok_or(EmissionError::DivisionByZero)?;
+        let dust = numerator
+            .checked_rem(total_weight)
+            .ok_or(EmissionError::DivisionByZero)?;
+        shares.push(u64::try_from(share).map_err(|_| EmissionError::ArithmeticOverflow)?);
+        *remainder = u64::try_from(dust).map_err(|_| EmissionError::ArithmeticOverflow)?;
+    }
+
+    Ok(shares)
+}
+
+/// Split `amount` between the buckets like [split_by_weight], then hand out the
+/// carried dust as whole lamports, largest remainder first (ties go to the
+/// earlier bucket), so that at most `limit` tokens are handed out in total.
+/// Used for the last drip, which must not leave any dust behind: the dust beyond
+/// `limit` is dropped.
+pub fn split_final_by_weight(
+    amount: u64,
+    weights: &[u64],
+    remainders: &mut [u64],
+    limit: u64,
+) -> Result<Vec<u64>, EmissionError> {
+    let mut shares = split_by_weight(amount, weights, remainders)?;
+
+    let handed_out = shares
+        .iter()
+        .try_fold(0u64, |sum, share| sum.checked_add(*share))
+        .ok_or(EmissionError::ArithmeticOverflow)?;
+    let mut leftover = owed_dust(weights, remainders)?.min(limit.saturating_sub(handed_out));
+    while leftover > 0 {
+        let mut largest = 0;
+        for i in 1..remainders.len() {
+            if remainders[i] > remainders[largest] {
+                largest = i;
+            }
+        }
+        shares[largest] = shares[largest]
+            .checked_add(1)
+            .ok_or(EmissionError::ArithmeticOverflow)?;
+        remainders[largest] = 0;
+        leftover -= 1;
+    }
+    for remainder in remainders.iter_mut() {
+        *remainder = 0;
+    }
+
+    Ok(shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Max supply of the faucets under test: 210 million tokens of 9 decimals.
+    const MAX_SUPPLY: u64 = 210_000_000_000_000_000;
+
+    /// 10 tokens per block for 100 blocks, 5 for 100 blocks, then 1 forever.
+    fn segments() -> Vec<Segment> {
+        vec![
+            Segment {
+                start_block_height: 0,
+                reward_per_block: 10,
+            },
+            Segment {
+                start_block_height: 100,
+                reward_per_block: 5,
+            },
+            Segment {
+                start_block_height: 200,
+                reward_per_block: 1,
+            },
+        ]
+    }
+
+    /// A faucet generating a block every 3 seconds since 1000, split 1:2:3.
+    fn state(segments: &[Segment]) -> EmissionState {
+        EmissionState {
+            segments: segments.to_vec(),
+            weights: vec![1, 2, 3],
+            remainders: vec![0, 0, 0],
+            supply: 0,
+            max_supply: MAX_SUPPLY,
+            finished: false,
+            paused: false,
+            clock_source: ClockSource::UnixTimestamp,
+            block_gen_rate: 3,
+            max_blocks_per_drip: 1_000,
+            forfeit_missed_blocks: false,
+            grace_blocks: 0,
+            cranker_reward_bps: 0,
+            max_cranker_reward: 0,
+            current_block_height: 0,
+            last_gen_block_timestamp: 1_000,
+            last_gen_block_slot: 0,
+            start_timestamp: 1_000,
+            end_timestamp: 0,
+            start_slot: 0,
+            end_slot: 0,
+        }
+    }
+
+    fn at(unix_timestamp: i64) -> Now {
+        Now {
+            unix_timestamp: unix_timestamp,
+            slot: 42,
+        }
+    }
+
+    /// Apply `plan` to `state` the way drip does.
+    fn apply(state: &mut EmissionState, plan: &DripPlan) {
+        state.supply = plan.supply;
+        state.finished = plan.cap_reached;
+        state.current_block_height = plan.current_block_height;
+        state.last_gen_block_timestamp = plan.last_gen_block_timestamp;
+        state.last_gen_block_slot = plan.last_gen_block_slot;
+        state.remainders = plan.remainders.clone();
+    }
+
+    /// Xorshift generator, so the property tests are reproducible without extra dependencies.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, bound: u64) -> u64 {
+            self.next() % bound
+        }
+    }
+
+    #[test]
+    fn era_and_reward_follows_segments() {
+        let segments = segments();
+        assert_eq!(era_and_reward(&segments, 0).unwrap(), (0, 10));
+        assert_eq!(era_and_reward(&segments, 99).unwrap(), (0, 10));
+        assert_eq!(era_and_reward(&segments, 100).unwrap(), (1, 5));
+        assert_eq!(era_and_reward(&segments, 199).unwrap(), (1, 5));
+        assert_eq!(era_and_reward(&segments, 200).unwrap(), (2, 1));
+        assert_eq!(era_and_reward(&segments, u64::MAX).unwrap(), (2, 1));
+    }
+
+    #[test]
+    fn block_rewards_straddle_segments() {
+        let segments = segments();
+        assert_eq!(block_rewards(&segments, 0, 0).unwrap(), 0);
+        assert_eq!(block_rewards(&segments, 0, 10).unwrap(), 100);
+        assert_eq!(block_rewards(&segments, 95, 10).unwrap(), 5 * 10 + 5 * 5);
+        assert_eq!(
+            block_rewards(&segments, 50, 200).unwrap(),
+            50 * 10 + 100 * 5 + 50
+        );
+        assert_eq!(block_rewards(&segments, 300, 7).unwrap(), 7);
+    }
+
+    #[test]
+    fn block_rewards_add_up() {
+        let segments = segments();
+        for height in 0..250 {
+            for num in 0..60 {
+                let split = block_rewards(&segments, height, num / 2).unwrap()
+                    + block_rewards(&segments, height + num / 2, num - num / 2).unwrap();
+                assert_eq!(block_rewards(&segments, height, num).unwrap(), split);
+            }
+        }
+    }
+
+    #[test]
+    fn cranker_reward_is_capped() {
+        assert_eq!(cranker_reward(1_000_000, 10, u64::MAX).unwrap(), 1_000);
+        assert_eq!(cranker_reward(1_000_000, 10, 500).unwrap(), 500);
+        assert_eq!(cranker_reward(999, 10, u64::MAX).unwrap(), 0);
+        assert_eq!(cranker_reward(1_000, 0, u64::MAX).unwrap(), 0);
+        assert_eq!(
+            cranker_reward(1_000, MAX_BASIS_POINTS, u64::MAX).unwrap(),
+            1_000
+        );
+        assert_eq!(
+            cranker_reward(u64::MAX, MAX_BASIS_POINTS, u64::MAX).unwrap(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn split_by_weight_carries_dust() {
+        let weights = [1, 2, 3];
+        let mut remainders = [0, 0, 0];
+
+        assert_eq!(
+            split_by_weight(10, &weights, &mut remainders).unwrap(),
+            vec![1, 3, 5]
+        );
+        assert_eq!(remainders, [4, 2, 0]);
+        assert_eq!(owed_dust(&weights, &remainders).unwrap(), 1);
+
+        // The carried dust makes up the missing lamport of the first bucket.
+        assert_eq!(
+            split_by_weight(2, &weights, &mut remainders).unwrap(),
+            vec![1, 1, 1]
+        );
+        assert_eq!(remainders, [0, 0, 0]);
+    }
+
+    #[test]
+    fn split_final_hands_out_dust() {
+        let weights = [1, 1, 1];
+        let mut remainders = [2, 2, 1];
+
+        // 5 / 3 lamports of dust: one whole lamport, to the earlier of the largest remainders.
+        assert_eq!(
+            split_final_by_weight(3, &weights, &mut remainders, u64::MAX).unwrap(),
+            vec![2, 1, 1]
+        );
+        assert_eq!(remainders, [0, 0, 0]);
+
+        // Past the limit, the dust is dropped.
+        let mut remainders = [2, 2, 1];
+        assert_eq!(
+            split_final_by_weight(3, &weights, &mut remainders, 3).unwrap(),
+            vec![1, 1, 1]
+        );
+        assert_eq!(remainders, [0, 0, 0]);
+    }
+
+    #[test]
+    fn drip_generates_pending_blocks() {
+        let segments = segments();
+        let plan = compute_drip(&state(&segments), at(1_031)).unwrap();
+
+        assert_eq!(plan.intervals, 31);
+        assert_eq!(plan.gen_block_num, 10);
+        assert_eq!(plan.backlog_block_num, 0);
+        assert_eq!(plan.current_block_height, 10);
+        assert_eq!((plan.current_era, plan.current_reward), (0, 10));
+        assert_eq!(plan.last_gen_block_timestamp, 1_030);
+        assert_eq!(plan.last_gen_block_slot, 42);
+        assert_eq!(plan.amounts, vec![16, 33, 50]);
+        assert_eq!(plan.remainders, vec![4, 2, 0]);
+        assert_eq!(plan.supply, 99);
+        assert!(!plan.cap_reached);
+    }
+
+    #[test]
+    fn drip_rewards_the_cranker_first() {
+        let segments = segments();
+        let mut state = state(&segments);
+        state.cranker_reward_bps = 1_000;
+        state.max_cranker_reward = 7;
+
+        let plan = compute_drip(&state, at(1_030)).unwrap();
+        assert_eq!(plan.cranker_reward, 7);
+        assert_eq!(plan.amounts, vec![15, 31, 46]);
+        assert_eq!(plan.remainders, vec![3, 0, 3]);
+        assert_eq!(plan.supply, 99);
+    }
+
+    #[test]
+    fn drip_leaves_a_backlog() {
+        let segments = segments();
+        let mut state = state(&segments);
+        state.max_blocks_per_drip = 4;
+
+        let plan = compute_drip(&state, at(1_030)).unwrap();
+        assert_eq!(plan.gen_block_num, 4);
+        assert_eq!(plan.backlog_block_num, 6);
+        assert_eq!(plan.current_block_height, 4);
+        assert_eq!(plan.last_gen_block_timestamp, 1_012);
+        assert_eq!(plan.supply, 39);
+    }
+
+    #[test]
+    fn drip_forfeits_missed_blocks() {
+        let segments = segments();
+        let mut state = state(&segments);
+        state.forfeit_missed_blocks = true;
+        state.grace_blocks = 2;
+        state.current_block_height = 98;
+
+        let plan = compute_drip(&state, at(1_030)).unwrap();
+        assert_eq!(plan.forfeited_block_num, 8);
+        assert_eq!(plan.forfeited_amount, 2 * 10 + 6 * 5);
+        assert_eq!(plan.gen_block_num, 2);
+        assert_eq!(plan.current_block_height, 108);
+        assert_eq!(plan.last_gen_block_timestamp, 1_030);
+        assert_eq!(plan.supply, 9);
+
+        // Within the grace blocks, nothing is forfeited.
+        let plan = compute_drip(&state, at(1_006)).unwrap();
+        assert_eq!(plan.forfeited_block_num, 0);
+        assert_eq!(plan.gen_block_num, 2);
+    }
+
+    #[test]
+    fn drip_stops_at_the_end() {
+        let segments = segments();
+        let mut state = state(&segments);
+        state.end_timestamp = 1_010;
+
+        let plan = compute_drip(&state, at(2_000)).unwrap();
+        assert_eq!(plan.gen_block_num, 3);
+        assert_eq!(plan.last_gen_block_timestamp, 1_009);
+
+        apply(&mut state, &plan);
+        assert!(matches!(
+            compute_drip(&state, at(2_000)),
+            Err(EmissionError::EmissionEnded)
+        ));
+    }
+
+    #[test]
+    fn pause_forfeits_only_the_emission_period() {
+        assert_eq!(forfeited_pause_time(150, 300, 0, 0).unwrap(), 150);
+        assert_eq!(forfeited_pause_time(50, 300, 100, 0).unwrap(), 200);
+        assert_eq!(forfeited_pause_time(50, 80, 100, 0).unwrap(), 0);
+        assert_eq!(forfeited_pause_time(150, 300, 0, 200).unwrap(), 50);
+        assert_eq!(forfeited_pause_time(250, 300, 0, 200).unwrap(), 0);
+        assert_eq!(forfeited_pause_slots(150, 300, 0, 200).unwrap(), 50);
+
+        // A block every 10 seconds, the last one at 100, paused from 150 to 300 with
+        // emission ending at 200: the 5 blocks pending before the pause are kept.
+        let segments = segments();
+        let mut state = state(&segments);
+        state.block_gen_rate = 10;
+        state.start_timestamp = 0;
+        state.last_gen_block_timestamp = 100;
+        state.end_timestamp = 200;
+        state.last_gen_block_timestamp += forfeited_pause_time(150, 300, 0, 200).unwrap();
+
+        let plan = compute_drip(&state, at(300)).unwrap();
+        assert_eq!(plan.gen_block_num, 5);
+        assert_eq!(plan.last_gen_block_timestamp, 200);
+    }
+
+    #[test]
+    fn drip_counts_slots() {
+        let segments = segments();
+        let mut state = state(&segments);
+        state.clock_source = ClockSource::Slot;
+        state.start_slot = 500;
+        state.last_gen_block_slot = 500;
+
+        let now = Now {
+            unix_timestamp: 77,
+            slot: 520,
+        };
+        let plan = compute_drip(&state, now).unwrap();
+        assert_eq!(plan.gen_block_num, 6);
+        assert_eq!(plan.last_gen_block_slot, 518);
+        assert_eq!(plan.last_gen_block_timestamp, 77);
+
+        let now = Now {
+            unix_timestamp: 77,
+            slot: 499,
+        };
+        assert!(matches!(
+            compute_drip(&state, now),
+            Err(EmissionError::EmissionNotStarted)
+        ));
+    }
+
+    #[test]
+    fn drip_rejects_invalid_states() {
+        let segments = segments();
+
+        let mut finished = state(&segments);
+        finished.finished = true;
+        assert!(matches!(
+            compute_drip(&finished, at(1_030)),
+            Err(EmissionError::FaucetFinished)
+        ));
+
+        let mut paused = state(&segments);
+        paused.paused = true;
+        assert!(matches!(
+            compute_drip(&paused, at(1_030)),
+            Err(EmissionError::Paused)
+        ));
+
+        let mut full = state(&segments);
+        full.supply = MAX_SUPPLY;
+        assert!(matches!(
+            compute_drip(&full, at(1_030)),
+            Err(EmissionError::TotalSupplyLimit)
+        ));
+
+        let mut not_started = state(&segments);
+        not_started.start_timestamp = 1_100;
+        assert!(matches!(
+            compute_drip(&not_started, at(1_030)),
+            Err(EmissionError::EmissionNotStarted)
+        ));
+
+        assert!(matches!(
+            compute_drip(&state(&segments), at(1_000)),
+            Err(EmissionError::InvalidTimestamp)
+        ));
+        assert!(matches!(
+            compute_drip(&state(&segments), at(1_002)),
+            Err(EmissionError::InsufficientInterval)
+        ));
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let segments = [Segment {
+            start_block_height: 0,
+            reward_per_block: u64::MAX,
+        }];
+        assert!(matches!(
+            block_rewards(&segments, 0, 2),
+            Err(EmissionError::ArithmeticOverflow)
+        ));
+        assert!(matches!(
+            block_rewards(&segments, u64::MAX, 1),
+            Err(EmissionError::ArithmeticOverflow)
+        ));
+        assert!(matches!(
+            compute_drip(&state(&segments), at(1_006)),
+            Err(EmissionError::ArithmeticOverflow)
+        ));
+
+        assert!(matches!(
+            cranker_reward(u64::MAX, MAX_BASIS_POINTS + 1, u64::MAX),
+            Err(EmissionError::ArithmeticOverflow)
+        ));
+        assert!(matches!(
+            split_by_weight(1, &[u64::MAX, 1], &mut [0, 0]),
+            Err(EmissionError::ArithmeticOverflow)
+        ));
+        assert!(matches!(
+            owed_dust(&[1], &[u64::MAX, 1]),
+            Err(EmissionError::ArithmeticOverflow)
+        ));
+    }
+
+    #[test]
+    fn division_by_zero_is_an_error() {
+        let segments = segments();
+        let mut state = state(&segments);
+        state.block_gen_rate = 0;
+        assert!(matches!(
+            compute_drip(&state, at(1_030)),
+            Err(EmissionError::DivisionByZero)
+        ));
+
+        let mut state = self::state(&segments);
+        state.weights = vec![0, 0, 0];
+        assert!(matches!(
+            compute_drip(&state, at(1_030)),
+            Err(EmissionError::DivisionByZero)
+        ));
+        assert!(matches!(
+            split_by_weight(10, &[0], &mut [0]),
+            Err(EmissionError::DivisionByZero)
+        ));
+        assert!(matches!(
+            owed_dust(&[], &[]),
+            Err(EmissionError::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    fn slot_out_of_range_is_an_error() {
+        let segments = segments();
+        let mut state = state(&segments);
+        state.clock_source = ClockSource::Slot;
+
+        let now = Now {
+            unix_timestamp: 0,
+            slot: u64::MAX,
+        };
+        assert!(matches!(
+            compute_drip(&state, now),
+            Err(EmissionError::TimestampConversion)
+        ));
+
+        state.last_gen_block_slot = i64::MAX as u64 + 1;
+        assert!(matches!(
+            compute_drip(&state, at(1_030)),
+            Err(EmissionError::TimestampConversion)
+        ));
+    }
+
+    #[test]
+    fn invalid_schedule_is_an_error() {
+        assert!(matches!(
+            era_and_reward(&[], 0),
+            Err(EmissionError::InvalidEmissionSchedule)
+        ));
+
+        let segments = [Segment {
+            start_block_height: 5,
+            reward_per_block: 10,
+        }];
+        assert!(matches!(
+            block_rewards(&segments, 0, 10),
+            Err(EmissionError::InvalidEmissionSchedule)
+        ));
+        assert!(matches!(
+            compute_drip(&state(&segments), at(1_030)),
+            Err(EmissionError::InvalidEmissionSchedule)
+        ));
+    }
+
+    #[test]
+    fn drip_reaches_the_cap_exactly() {
+        let segments = [Segment {
+            start_block_height: 0,
+            reward_per_block: MAX_SUPPLY / 7,
+        }];
+        let mut state = state(&segments);
+        state.weights = vec![3, 5, 11];
+        state.cranker_reward_bps = 25;
+        state.max_cranker_reward = u64::MAX;
+
+        let mut timestamp = 1_000;
+        loop {
+            timestamp += 3;
+            let plan = compute_drip(&state, at(timestamp)).unwrap();
+            apply(&mut state, &plan);
+            if plan.cap_reached {
+                break;
+            }
+        }
+
+        assert_eq!(state.supply, MAX_SUPPLY);
+        assert_eq!(state.remainders, vec![0, 0, 0]);
+        assert!(matches!(
+            compute_drip(&state, at(timestamp + 3)),
+            Err(EmissionError::TotalSupplyLimit)
+        ));
+    }
+
+    #[test]
+    fn drip_ends_when_the_dust_exceeds_the_headroom() {
+        let segments = segments();
+        let mut state = state(&segments);
+        state.remainders = vec![5, 5, 5];
+
+        // 2 lamports of dust are owed, but other faucets left room for only 1.
+        state.supply = MAX_SUPPLY - 1;
+        let plan = compute_drip(&state, at(1_003)).unwrap();
+        assert!(plan.cap_reached);
+        assert_eq!(plan.cranker_reward, 0);
+        assert_eq!(plan.amounts, vec![1, 0, 0]);
+        assert_eq!(plan.remainders, vec![0, 0, 0]);
+        assert_eq!(plan.supply, MAX_SUPPLY);
+
+        // No room at all.
+        state.supply = MAX_SUPPLY;
+        assert!(matches!(
+            compute_drip(&state, at(1_003)),
+            Err(EmissionError::TotalSupplyLimit)
+        ));
+    }
+
+    #[test]
+    fn prop_split_conserves_tokens() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..1_000 {
+            let count = 1 + rng.below(16) as usize;
+            let weights: Vec<u64> = (0..count).map(|_| 1 + rng.below(1_000)).collect();
+            let total_weight: u64 = weights.iter().sum();
+            let mut remainders = vec![0; count];
+
+            let mut emitted = 0u64;
+            let mut received = vec![0u64; count];
+            for _ in 0..20 {
+                let amount = rng.below(1 << 40);
+                emitted += amount;
+                let shares = split_by_weight(amount, &weights, &mut remainders).unwrap();
+                for i in 0..count {
+                    received[i] += shares[i];
+                    assert!(remainders[i] < total_weight);
+                }
+
+                // Every lamport is either received or owed as dust.
+                let owed: u64 = remainders.iter().sum();
+                assert_eq!(
+                    received.iter().sum::<u64>() as u128 * total_weight as u128 + owed as u128,
+                    emitted as u128 * total_weight as u128
+                );
+            }
+
+            let amount = rng.below(1 << 40);
+            emitted += amount;
+            let shares =
+                split_final_by_weight(amount, &weights, &mut remainders, u64::MAX).unwrap();
+            for i in 0..count {
+                received[i] += shares[i];
+            }
+            assert_eq!(remainders, vec![0; count]);
+            assert_eq!(received.iter().sum::<u64>(), emitted);
+        }
+    }
+
+    #[test]
+    fn prop_drips_never_exceed_the_cap() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..200 {
+            let mut segments = Vec::new();
+            let mut start_block_height = 0;
+            for _ in 0..1 + rng.below(8) {
+                segments.push(Segment {
+                    start_block_height: start_block_height,
+                    reward_per_block: rng.below(MAX_SUPPLY / 50),
+                });
+                start_block_height += 1 + rng.below(50);
+            }
+
+            let mut state = state(&segments);
+            let count = 1 + rng.below(16) as usize;
+            state.weights = (0..count).map(|_| 1 + rng.below(1_000)).collect();
+            state.remainders = vec![0; count];
+            state.supply = rng.below(MAX_SUPPLY / 2);
+            state.block_gen_rate = 1 + rng.below(10) as u32;
+            state.max_blocks_per_drip = 1 + rng.below(100);
+            state.forfeit_missed_blocks = rng.below(2) == 1;
+            state.grace_blocks = rng.below(20);
+            state.cranker_reward_bps = rng.below(MAX_BASIS_POINTS as u64 + 1) as u16;
+            state.max_cranker_reward = rng.below(MAX_SUPPLY);
+
+            let mut timestamp = state.last_gen_block_timestamp;
+            for _ in 0..100 {
+                timestamp += rng.below(200) as i64;
+                let plan = match compute_drip(&state, at(timestamp)) {
+                    Ok(plan) => plan,
+                    Err(EmissionError::InvalidTimestamp)
+                    | Err(EmissionError::InsufficientInterval) => continue,
+                    Err(EmissionError::TotalSupplyLimit) | Err(EmissionError::FaucetFinished) => {
+                        break
+                    }
+                    Err(_) => panic!("unexpected drip error"),
+                };
+
+                let drip_amount = plan.amounts.iter().sum::<u64>() + plan.cranker_reward;
+                assert_eq!(plan.supply, state.supply + drip_amount);
+                assert!(plan.supply <= MAX_SUPPLY);
+                assert_eq!(plan.cap_reached, plan.supply == MAX_SUPPLY);
+                assert_eq!(
+                    plan.current_block_height,
+                    state.current_block_height + plan.forfeited_block_num + plan.gen_block_num
+                );
+                assert!(plan.gen_block_num <= state.max_blocks_per_drip);
+                assert!(plan.last_gen_block_timestamp <= timestamp);
+                assert!(
+                    timestamp - plan.last_gen_block_timestamp
+                        < (plan.backlog_block_num as i64 + 1) * state.block_gen_rate as i64
+                );
+                if !plan.cap_reached {
+                    // Every lamport of the generated blocks is either accrued or owed as dust.
+                    let total_weight: u64 = state.weights.iter().sum();
+                    let rewards = block_rewards(
+                        &segments,
+                        state.current_block_height + plan.forfeited_block_num,
+                        plan.gen_block_num,
+                    )
+                    .unwrap();
+                    let dust_before: u64 = state.remainders.iter().sum();
+                    let dust_after: u64 = plan.remainders.iter().sum();
+                    assert_eq!(
+                        (drip_amount as u128) * total_weight as u128 + dust_after as u128,
+                        (rewards as u128) * total_weight as u128 + dust_before as u128
+                    );
+                }
+
+                apply(&mut state, &plan);
+                if plan.cap_reached {
+                    assert_eq!(state.remainders, vec![0; count]);
+                }
+            }
+        }
+    }
+}
@@ -5,23 +5,23 @@
 */
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::{IsInitialized, Pack};
 use anchor_lang::solana_program::system_program;
 use anchor_spl::token::{self, Mint};
+use emission::{EmissionState, Now};
 use std::convert::TryFrom;
 
+pub mod emission;
+
 declare_id!("Ce3x2Wup3hNWDcNnLuXRjB9msb8aRn6xX8U5aq1fTYrN");
 
 #[constant]
 pub const DECIMALS: u8 = 9; // Token decimals is 9.
-pub const BLOCK_GEN_RATE: u8 = 3; // Generate a block every three seconds.
 pub const MAX_TOTAL_SUPPLY: u64 = 210_000_000 * 10_u64.pow(DECIMALS as u32); // The supply of tokens is capped at 210 million.
-pub const COIN_NUM_PER_BLOCK: u64 = 3 * 10_u64.pow(DECIMALS as u32); // Per block contains 3 tokens.
-pub const ARENA_PERCENTAGE: f64 = 0.07352941176470588235294117647059; // Percentage of arena.
-pub const NFT_MINING_PERCENTAGE: f64 = 0.17647058823529411764705882352941; // Percentage of NFT minting.
-pub const LIQUIDITY_MINING_PERCENTAGE: f64 = 0.19117647058823529411764705882353; // Percentage of liquidity minting.
-pub const MARKETING_PERCENTAGE: f64 = 0.04411764705882352941176470588235; // Percentage of NFT trading Market.
-pub const ECOSYSTEM_PERCENTAGE: f64 = 0.29411764705882352941176470588235; // Percentage of ecosystem.
-pub const GYC_STAKING_PERCENTAGE: f64 = 0.22058823529411764705882352941176; // Percentage of GYC staking.
+pub const MAX_EMISSION_SEGMENTS: usize = 64; // The emission schedule holds at most 64 segments.
+pub const MAX_BUCKETS: usize = 16; // The config holds at most 16 distribution buckets.
+pub const MAX_BASIS_POINTS: u16 = emission::MAX_BASIS_POINTS; // Basis points of a whole drip.
 
 #[program]
 pub mod token_faucet {
@@ -30,35 +30,162 @@ pub mod token_faucet {
     // Initialize configuration account.
     /**
      * @param ctx : Initialize context
-     * @param bump : Bump seed for program address.
+     * @param faucet_id : Id telling apart the faucets of the same mint, 0 by default.
+     * @param params : Settings of the faucet; the recipient's associated token accounts
+     *   of the buckets are passed as remaining accounts in the order of the weights.
      */
-    pub fn initialize(ctx: Context<Initialize>, bump: u8) -> ProgramResult {
-        // Determine if the recipient's associated token account has been created; if not, return the corresponding error.
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        faucet_id: Option<u64>,
+        params: InitializeParams,
+    ) -> ProgramResult {
+        let faucet_id = faucet_id.unwrap_or(0);
+        let InitializeParams {
+            block_gen_rate,
+            max_blocks_per_drip,
+            grace_blocks,
+            cranker_reward_bps,
+            max_cranker_reward,
+            clock_source,
+            start_timestamp,
+            end_timestamp,
+            start_slot,
+            end_slot,
+            schedule,
+            weights,
+            check_freeze_authority,
+        } = params;
+        let segments = schedule.segments()?;
+
+        // Check emission period validity, it is given in the units of the clock source.
+        match clock_source {
+            ClockSource::UnixTimestamp => require!(
+                start_slot.is_none() && end_slot.is_none(),
+                StatusInfo::InvalidParamError
+            ),
+            ClockSource::Slot => require!(
+                start_timestamp.is_none() && end_timestamp.is_none(),
+                StatusInfo::InvalidParamError
+            ),
+        }
+        let start_slot = start_slot.unwrap_or(ctx.accounts.clock.slot);
+        require!(
+            start_slot >= ctx.accounts.clock.slot,
+            StatusInfo::InvalidSlot
+        );
+        if let Some(end_slot) = end_slot {
+            require!(end_slot > start_slot, StatusInfo::InvalidSlot);
+        }
+        let start_timestamp = start_timestamp.unwrap_or(ctx.accounts.clock.unix_timestamp);
         require!(
-            !ctx.accounts.receiver_arena.data_is_empty(),
-            StatusInfo::NotInitializedAssociatedTokenAccount
+            start_timestamp >= ctx.accounts.clock.unix_timestamp,
+            StatusInfo::InvalidTimestamp
         );
+        if let Some(end_timestamp) = end_timestamp {
+            require!(
+                end_timestamp > start_timestamp,
+                StatusInfo::InvalidTimestamp
+            );
+        }
+
+        // Check emission schedule validity.
+        require!(block_gen_rate > 0, StatusInfo::InvalidParamError);
+        require!(max_blocks_per_drip > 0, StatusInfo::InvalidParamError);
+        require!(grace_blocks != Some(0), StatusInfo::InvalidParamError);
         require!(
-            !ctx.accounts.receiver_nft_mining.data_is_empty(),
-            StatusInfo::NotInitializedAssociatedTokenAccount
+            cranker_reward_bps <= MAX_BASIS_POINTS,
+            StatusInfo::InvalidParamError
         );
         require!(
-            !ctx.accounts.receiver_liquidity_mining.data_is_empty(),
-            StatusInfo::NotInitializedAssociatedTokenAccount
+            !segments.is_empty() && segments.len() <= MAX_EMISSION_SEGMENTS,
+            StatusInfo::InvalidEmissionSchedule
         );
         require!(
-            !ctx.accounts.receiver_marketing.data_is_empty(),
-            StatusInfo::NotInitializedAssociatedTokenAccount
+            segments[0].start_block_height == 0,
+            StatusInfo::InvalidEmissionSchedule
         );
         require!(
-            !ctx.accounts.receiver_ecosystem.data_is_empty(),
-            StatusInfo::NotInitializedAssociatedTokenAccount
+            segments
+                .windows(2)
+                .all(|pair| pair[0].start_block_height < pair[1].start_block_height),
+            StatusInfo::InvalidEmissionSchedule
+        );
+
+        // Derive the canonical bump seeds of both program addresses.
+        let mint_key = ctx.accounts.mint.key();
+        let (_, config_bump) = Pubkey::find_program_address(
+            &[
+                b"GameYoo-Token".as_ref(),
+                mint_key.as_ref(),
+                &faucet_id.to_le_bytes(),
+            ],
+            ctx.program_id,
+        );
+        let (mint_authority, mint_authority_bump) = Pubkey::find_program_address(
+            &[b"GYC-Mint-Auth".as_ref(), mint_key.as_ref()],
+            ctx.program_id,
+        );
+        let (_, mint_ledger_bump) = Pubkey::find_program_address(
+            &[b"Mint-Ledger".as_ref(), mint_key.as_ref()],
+            ctx.program_id,
         );
         require!(
-            !ctx.accounts.receiver_gyc_staking.data_is_empty(),
-            StatusInfo::NotInitializedAssociatedTokenAccount
+            mint_authority == *ctx.accounts.mint_authority.key,
+            StatusInfo::InvalidTokenAuthority
         );
 
+        // Check the mint has been handed over to the program.
+        let mint = &ctx.accounts.mint;
+        require!(
+            mint.mint_authority == COption::Some(mint_authority),
+            StatusInfo::InvalidTokenAuthority
+        );
+        require!(mint.decimals == DECIMALS, StatusInfo::InvalidMintDecimals);
+        require!(
+            ctx.accounts.mint_ledger.supply(mint.supply)? < MAX_TOTAL_SUPPLY,
+            StatusInfo::TotalSupplyLimit
+        );
+        if check_freeze_authority {
+            require!(
+                mint.freeze_authority == COption::None
+                    || mint.freeze_authority == COption::Some(mint_authority),
+                StatusInfo::InvalidFreezeAuthority
+            );
+        }
+
+        // Check distribution buckets validity.
+        let receivers = ctx.remaining_accounts;
+        require!(
+            !weights.is_empty() && weights.len() <= MAX_BUCKETS,
+            StatusInfo::InvalidBucket
+        );
+        require!(
+            weights.iter().all(|weight| *weight > 0),
+            StatusInfo::InvalidBucket
+        );
+        require!(
+            weights
+                .iter()
+                .try_fold(0u64, |sum, weight| sum.checked_add(*weight))
+                .is_some(),
+            StatusInfo::InvalidBucket
+        );
+        require!(
+            receivers.len() == weights.len(),
+            StatusInfo::InvalidReceiverTokenAccount
+        );
+
+        // Determine if the recipient's associated token accounts are usable token accounts of the mint; if not, return the corresponding error.
+        for receiver in receivers.iter() {
+            validate_receiver(receiver, ctx.accounts.mint.to_account_info().key)?;
+        }
+        require_distinct_receivers(
+            &receivers
+                .iter()
+                .map(|receiver| *receiver.key)
+                .collect::<Vec<Pubkey>>(),
+        )?;
+
         let config_account = &mut ctx.accounts.config_account;
 
         /*
@@ -70,37 +197,96 @@ pub mod token_faucet {
         // Record token authority address.
         config_account.mint_authority = *ctx.accounts.mint_authority.key;
 
-        // Record the recipient's associated token account address.
-        config_account.receiver_arena = *ctx.accounts.receiver_arena.key;
-        config_account.receiver_nft_mining = *ctx.accounts.receiver_nft_mining.key;
-        config_account.receiver_liquidity_mining = *ctx.accounts.receiver_liquidity_mining.key;
-        config_account.receiver_marketing = *ctx.accounts.receiver_marketing.key;
-        config_account.receiver_ecosystem = *ctx.accounts.receiver_ecosystem.key;
-        config_account.receiver_gyc_staking = *ctx.accounts.receiver_gyc_staking.key;
-
-        // Record the magic number.
+        // Record the admin authority of the config, no transfer is pending yet.
+        config_account.authority = *ctx.accounts.payer.key;
+        config_account.pending_authority = Pubkey::default();
+
+        // Record the distribution buckets, no rounding dust has been carried over yet.
+        config_account.bucket_count =
+            u8::try_from(weights.len()).map_err(|_| StatusInfo::InvalidBucket)?;
+        for (i, (receiver, weight)) in receivers.iter().zip(weights.iter()).enumerate() {
+            config_account.buckets[i] = Bucket {
+                receiver: *receiver.key,
+                weight: *weight,
+                remainder: 0,
+                accrued: 0,
+                total_accrued: 0,
+            };
+        }
+
+        // Record the magic number and the faucet id.
         config_account.magic = 0x544b4654;
+        config_account.faucet_id = faucet_id;
 
-        // Record bump seed for program address.
-        config_account.bump = bump;
+        // Record bump seeds for program addresses.
+        config_account.config_bump = config_bump;
+        config_account.mint_authority_bump = mint_authority_bump;
 
-        // Record initial block height.
-        config_account.current_block_height = 0;
+        // Emission has not reached the max supply yet and is not paused.
+        config_account.finished = false;
+        config_account.paused = false;
+        config_account.paused_timestamp = 0;
+        config_account.paused_slot = 0;
+
+        // Record the emission schedule.
+        config_account.block_gen_rate = block_gen_rate;
+        config_account.max_blocks_per_drip = max_blocks_per_drip;
+
+        // Record the forfeit mode, nothing has been forfeited yet.
+        config_account.forfeit_missed_blocks = grace_blocks.is_some();
+        config_account.grace_blocks = grace_blocks.unwrap_or(0);
+        config_account.forfeited_block_num = 0;
+        config_account.forfeited_amount = 0;
 
-        // Record the timestamp of the last generated block.
-        config_account.last_gen_block_timestamp = ctx.accounts.clock.unix_timestamp;
+        // Nothing has been emitted yet.
+        config_account.total_emitted = 0;
+
+        // Record the reward of whoever calls drip.
+        config_account.cranker_reward_bps = cranker_reward_bps;
+        config_account.max_cranker_reward = max_cranker_reward;
+        config_account.emission_schedule = ctx.accounts.emission_schedule.key();
+
+        // Record initial block height, era and block reward.
+        config_account.current_block_height = 0;
+        config_account.current_era = 0;
+        config_account.current_reward = segments[0].reward_per_block;
+
+        // Record the emission period, the first block is generated one block after the start.
+        config_account.clock_source = clock_source;
+        config_account.start_timestamp = start_timestamp;
+        config_account.end_timestamp = end_timestamp.unwrap_or(0);
+        config_account.start_slot = start_slot;
+        config_account.end_slot = end_slot.unwrap_or(0);
+
+        // Record the timestamp and the slot of the last generated block.
+        config_account.last_gen_block_timestamp = start_timestamp;
+        config_account.last_gen_block_slot = start_slot;
+
+        let emission_schedule = &mut ctx.accounts.emission_schedule;
+        emission_schedule.config_account = config_account.key();
+        emission_schedule.segments = segments;
+
+        // The first faucet of the mint creates the ledger shared by all of them,
+        // recording the supply minted before; nothing has been emitted or burned yet.
+        let mint_ledger = &mut ctx.accounts.mint_ledger;
+        if mint_ledger.mint == Pubkey::default() {
+            mint_ledger.mint = mint_key;
+            mint_ledger.bump = mint_ledger_bump;
+            mint_ledger.pre_mint_supply = ctx.accounts.mint.supply;
+            mint_ledger.total_emitted = 0;
+            mint_ledger.outstanding = 0;
+            mint_ledger.burned_amount = 0;
+        }
 
         emit!(InitializeEvent {
             status_code: StatusInfo::Ok as u64,
             status_desc: "Ok".to_string(),
             mint: *ctx.accounts.mint.to_account_info().key,
+            faucet_id: faucet_id,
             mint_authority: *ctx.accounts.mint_authority.key,
-            receiver_arena: *ctx.accounts.receiver_arena.key,
-            receiver_nft_mining: *ctx.accounts.receiver_nft_mining.key,
-            receiver_liquidity_mining: *ctx.accounts.receiver_liquidity_mining.key,
-            receiver_marketing: *ctx.accounts.receiver_marketing.key,
-            receiver_ecosystem: *ctx.accounts.receiver_ecosystem.key,
-            receiver_gyc_staking: *ctx.accounts.receiver_gyc_staking.key,
+            authority: *ctx.accounts.payer.key,
+            receivers: receivers.iter().map(|receiver| *receiver.key).collect(),
+            weights: weights,
             current_block_height: config_account.current_block_height,
             last_gen_block_timestamp: config_account.last_gen_block_timestamp,
             timestamp: Clock::get()?.unix_timestamp,
@@ -109,124 +295,468 @@ pub mod token_faucet {
         Ok(())
     }
 
-    /// Mint tokens for recipients according to the corresponding ratio.
-    /// This can be called by anyone.
+    /// Accrue the tokens of the generated blocks to the buckets according to the corresponding ratio.
+    /// Nothing is minted to the buckets; each bucket claims its accrual with [claim_bucket].
+    /// This can be called by anyone, the caller is rewarded with a share of the drip.
     /**
      * @param ctx : Drip context
      */
     pub fn drip(ctx: Context<Drip>) -> ProgramResult {
-        let current_time = Clock::get()?.unix_timestamp;
+        // Calculate the blocks to generate and the tokens to distribute.
+        // The cap counts the accruals of every faucet of the mint.
+        let clock = Clock::get()?;
+        let plan = emission::compute_drip(
+            &ctx.accounts.config_account.emission_state(
+                &ctx.accounts.emission_schedule.segments,
+                ctx.accounts.mint_ledger.supply(ctx.accounts.mint.supply)?,
+            )?,
+            Now {
+                unix_timestamp: clock.unix_timestamp,
+                slot: clock.slot,
+            },
+        )
+        .map_err(StatusInfo::from)?;
+        let config_account = &mut ctx.accounts.config_account;
 
-        // Check if token supply exceeds.
-        require!(
-            ctx.accounts.mint.supply < MAX_TOTAL_SUPPLY,
-            StatusInfo::TotalSupplyLimit
-        );
+        // Record the forfeited blocks.
+        config_account.forfeited_block_num = config_account
+            .forfeited_block_num
+            .checked_add(plan.forfeited_block_num)
+            .ok_or(StatusInfo::ArithmeticOverflow)?;
+        config_account.forfeited_amount = config_account
+            .forfeited_amount
+            .checked_add(plan.forfeited_amount)
+            .ok_or(StatusInfo::ArithmeticOverflow)?;
+
+        // The last drip ends emission.
+        if plan.cap_reached {
+            config_account.finished = true;
+        }
+
+        // Update block height, era and block reward of the next block.
+        config_account.current_block_height = plan.current_block_height;
+        config_account.current_era = plan.current_era;
+        config_account.current_reward = plan.current_reward;
+
+        // Update the timestamp and the slot of the latest block generation.
+        config_account.last_gen_block_timestamp = plan.last_gen_block_timestamp;
+        config_account.last_gen_block_slot = plan.last_gen_block_slot;
+
+        // Accrue the tokens of each bucket, to be claimed by its recipient.
+        for (i, bucket) in config_account.buckets_mut().iter_mut().enumerate() {
+            bucket.remainder = plan.remainders[i];
+            bucket.accrued = bucket
+                .accrued
+                .checked_add(plan.amounts[i])
+                .ok_or(StatusInfo::ArithmeticOverflow)?;
+            bucket.total_accrued = bucket
+                .total_accrued
+                .checked_add(plan.amounts[i])
+                .ok_or(StatusInfo::ArithmeticOverflow)?;
+        }
+        let accrued_amounts = plan
+            .amounts
+            .iter()
+            .try_fold(0u64, |sum, amount| sum.checked_add(*amount))
+            .ok_or(StatusInfo::ArithmeticOverflow)?;
+        let mint_ledger = &mut ctx.accounts.mint_ledger;
+        mint_ledger.outstanding = mint_ledger
+            .outstanding
+            .checked_add(accrued_amounts)
+            .ok_or(StatusInfo::ArithmeticOverflow)?;
+        let bucket_totals: Vec<u64> = config_account
+            .buckets()
+            .iter()
+            .map(|bucket| bucket.total_accrued)
+            .collect();
+
+        // Update the totals emitted by the faucet and by all the faucets of the mint.
+        let final_amounts = accrued_amounts
+            .checked_add(plan.cranker_reward)
+            .ok_or(StatusInfo::ArithmeticOverflow)?;
+        config_account.total_emitted = config_account
+            .total_emitted
+            .checked_add(final_amounts)
+            .ok_or(StatusInfo::ArithmeticOverflow)?;
+        mint_ledger.total_emitted = mint_ledger
+            .total_emitted
+            .checked_add(final_amounts)
+            .ok_or(StatusInfo::ArithmeticOverflow)?;
+
+        // Minting the reward for the cranker.
+        if plan.cranker_reward > 0 {
+            validate_receiver(&ctx.accounts.cranker, &ctx.accounts.config_account.mint)?;
+            token_mint_to(
+                &ctx.accounts.config_account,
+                &ctx.accounts.token_program,
+                &ctx.accounts.mint,
+                &ctx.accounts.mint_authority,
+                &ctx.accounts.cranker,
+                plan.cranker_reward,
+            )?;
+        }
+
+        emit!(DripEvent {
+            status_code: StatusInfo::Ok as u64,
+            status_desc: "Ok".to_string(),
+            mint: ctx.accounts.mint.to_account_info().key().clone(),
+            faucet_id: ctx.accounts.config_account.faucet_id,
+            current_block_height: plan.current_block_height,
+            current_era: plan.current_era,
+            current_reward: plan.current_reward,
+            last_gen_block_timestamp: plan.last_gen_block_timestamp,
+            last_gen_block_slot: plan.last_gen_block_slot,
+            amounts: plan.amounts.clone(),
+            cranker: ctx.accounts.cranker.key(),
+            cranker_reward: plan.cranker_reward,
+            bucket_totals: bucket_totals,
+            total_emitted: ctx.accounts.config_account.total_emitted,
+            intervals: plan.intervals,
+            backlog_block_num: plan.backlog_block_num,
+            forfeited_block_num: plan.forfeited_block_num,
+            forfeited_amount: plan.forfeited_amount,
+            total_forfeited_block_num: ctx.accounts.config_account.forfeited_block_num,
+            total_forfeited_amount: ctx.accounts.config_account.forfeited_amount,
+            supply: plan.supply,
+            timestamp: clock.unix_timestamp,
+        });
+
+        if plan.cap_reached {
+            emit!(CapReachedEvent {
+                status_code: StatusInfo::Ok as u64,
+                status_desc: "Ok".to_string(),
+                mint: ctx.accounts.mint.to_account_info().key().clone(),
+                faucet_id: ctx.accounts.config_account.faucet_id,
+                current_block_height: plan.current_block_height,
+                last_gen_block_timestamp: plan.last_gen_block_timestamp,
+                final_amounts: final_amounts,
+                supply: plan.supply,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        Ok(())
+    }
 
-        // Check current timestamp validity.
+    /// Calculate what drip would do now, without changing anything.
+    /// The [DripPlan] is returned as return data, so it can be read by simulating the transaction.
+    /// This can be called by anyone.
+    /**
+     * @param ctx : PreviewDrip context
+     */
+    pub fn preview_drip(ctx: Context<PreviewDrip>) -> ProgramResult {
+        let clock = Clock::get()?;
+        let plan = emission::compute_drip(
+            &ctx.accounts.config_account.emission_state(
+                &ctx.accounts.emission_schedule.segments,
+                ctx.accounts.mint_ledger.supply(ctx.accounts.mint.supply)?,
+            )?,
+            Now {
+                unix_timestamp: clock.unix_timestamp,
+                slot: clock.slot,
+            },
+        )
+        .map_err(StatusInfo::from)?;
+        solana_program::program::set_return_data(&DripPlan::from(plan).try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// Mint the outstanding accrual of a bucket to its recipient's associated token account.
+    /// Nothing is minted past [MAX_TOTAL_SUPPLY]; what does not fit stays accrued.
+    /// This can be called by anyone.
+    /**
+     * @param ctx : ClaimBucket context
+     * @param bucket : Index of the bucket.
+     */
+    pub fn claim_bucket(ctx: Context<ClaimBucket>, bucket: u8) -> ProgramResult {
         let config_account = &mut ctx.accounts.config_account;
+        let bucket_info = config_account
+            .buckets_mut()
+            .get_mut(bucket as usize)
+            .ok_or(StatusInfo::InvalidBucket)?;
+
+        // Check the recipient's associated token account is the bucket's and is still usable.
         require!(
-            current_time > config_account.last_gen_block_timestamp,
-            StatusInfo::InvalidTimestamp
+            ctx.accounts.receiver.key() == bucket_info.receiver,
+            StatusInfo::InvalidReceiverTokenAccount
         );
+        require!(bucket_info.accrued > 0, StatusInfo::NothingToClaim);
+        let amount = bucket_info
+            .accrued
+            .min(MAX_TOTAL_SUPPLY.saturating_sub(ctx.accounts.mint.supply));
+        require!(amount > 0, StatusInfo::TotalSupplyLimit);
+        bucket_info.accrued = bucket_info
+            .accrued
+            .checked_sub(amount)
+            .ok_or(StatusInfo::ArithmeticOverflow)?;
+        validate_receiver(&ctx.accounts.receiver, &config_account.mint)?;
+
+        // The claimed tokens are no longer outstanding.
+        let mint_ledger = &mut ctx.accounts.mint_ledger;
+        mint_ledger.outstanding = mint_ledger
+            .outstanding
+            .checked_sub(amount)
+            .ok_or(StatusInfo::ArithmeticOverflow)?;
+
+        // Minting tokens for the recipient.
+        token_mint_to(
+            &ctx.accounts.config_account,
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.receiver,
+            amount,
+        )?;
+
+        // Read the supply after minting.
+        ctx.accounts.mint.reload()?;
+
+        emit!(ClaimEvent {
+            status_code: StatusInfo::Ok as u64,
+            status_desc: "Ok".to_string(),
+            mint: ctx.accounts.mint.key(),
+            faucet_id: ctx.accounts.config_account.faucet_id,
+            bucket: bucket,
+            receiver: ctx.accounts.receiver.key(),
+            amount: amount,
+            supply: ctx.accounts.mint.supply,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Propose a new authority of the config; it takes over once it accepts.
+    /// Proposing the default pubkey cancels a pending proposal.
+    /**
+     * @param ctx : UpdateConfig context
+     * @param new_authority : The proposed authority.
+     */
+    pub fn propose_authority(ctx: Context<UpdateConfig>, new_authority: Pubkey) -> ProgramResult {
+        let config_account = &mut ctx.accounts.config_account;
+        config_account.pending_authority = new_authority;
+
+        emit!(AuthorityProposedEvent {
+            status_code: StatusInfo::Ok as u64,
+            status_desc: "Ok".to_string(),
+            mint: config_account.mint,
+            faucet_id: config_account.faucet_id,
+            authority: config_account.authority,
+            pending_authority: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Check call interval.
-        let intervals = current_time
-            .checked_sub(config_account.last_gen_block_timestamp)
-            .unwrap();
+        Ok(())
+    }
+
+    /// Pause drip. Only the authority can call it.
+    /**
+     * @param ctx : UpdateConfig context
+     */
+    pub fn pause(ctx: Context<UpdateConfig>) -> ProgramResult {
+        let config_account = &mut ctx.accounts.config_account;
+        require!(!config_account.paused, StatusInfo::Paused);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        config_account.paused = true;
+        config_account.paused_timestamp = current_time;
+        config_account.paused_slot = Clock::get()?.slot;
+
+        emit!(PauseEvent {
+            status_code: StatusInfo::Ok as u64,
+            status_desc: "Ok".to_string(),
+            mint: config_account.mint,
+            faucet_id: config_account.faucet_id,
+            authority: config_account.authority,
+            paused: true,
+            forfeited_seconds: 0,
+            forfeited_slots: 0,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Unpause drip. Only the authority can call it.
+    /// The time elapsed while paused is forfeited: the time of the last block generation, in the
+    /// clock source, is moved forward by the paused duration, so no blocks are generated
+    /// for it, while the blocks pending before the pause are kept.
+    /// Only the part of the pause after the start and before the end of emission is forfeited.
+    /**
+     * @param ctx : UpdateConfig context
+     */
+    pub fn unpause(ctx: Context<UpdateConfig>) -> ProgramResult {
+        let config_account = &mut ctx.accounts.config_account;
+        require!(config_account.paused, StatusInfo::NotPaused);
+
+        // No block is due before the start or after the end, so a pause only counts
+        // during emission.
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+        // Only the clock source counts blocks; the other clock records when the last drip took place.
+        let (forfeited_seconds, forfeited_slots) = match config_account.clock_source {
+            ClockSource::UnixTimestamp => {
+                let forfeited_seconds = emission::forfeited_pause_time(
+                    config_account.paused_timestamp,
+                    current_time,
+                    config_account.start_timestamp,
+                    config_account.end_timestamp,
+                )
+                .map_err(StatusInfo::from)?;
+                config_account.last_gen_block_timestamp = config_account
+                    .last_gen_block_timestamp
+                    .checked_add(forfeited_seconds)
+                    .ok_or(StatusInfo::ArithmeticOverflow)?;
+                (forfeited_seconds, 0)
+            }
+            ClockSource::Slot => {
+                let forfeited_slots = emission::forfeited_pause_slots(
+                    config_account.paused_slot,
+                    clock.slot,
+                    config_account.start_slot,
+                    config_account.end_slot,
+                )
+                .map_err(StatusInfo::from)?;
+                config_account.last_gen_block_slot = config_account
+                    .last_gen_block_slot
+                    .checked_add(forfeited_slots)
+                    .ok_or(StatusInfo::ArithmeticOverflow)?;
+                (0, forfeited_slots)
+            }
+        };
+        config_account.paused = false;
+        config_account.paused_timestamp = 0;
+        config_account.paused_slot = 0;
+
+        emit!(PauseEvent {
+            status_code: StatusInfo::Ok as u64,
+            status_desc: "Ok".to_string(),
+            mint: config_account.mint,
+            faucet_id: config_account.faucet_id,
+            authority: config_account.authority,
+            paused: false,
+            forfeited_seconds: forfeited_seconds,
+            forfeited_slots: forfeited_slots,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Replace the recipient's associated token account of a bucket.
+    /// Only the authority can call it.
+    /**
+     * @param ctx : SetReceiver context
+     * @param bucket : Index of the bucket.
+     */
+    pub fn set_receiver(ctx: Context<SetReceiver>, bucket: u8) -> ProgramResult {
+        let new_receiver = ctx.accounts.new_receiver.key();
+        let config_account = &mut ctx.accounts.config_account;
+        validate_receiver(&ctx.accounts.new_receiver, &config_account.mint)?;
         require!(
-            intervals >= i64::try_from(BLOCK_GEN_RATE).unwrap(),
-            StatusInfo::InsufficientIntervalError
+            config_account
+                .buckets()
+                .iter()
+                .all(|existing| existing.receiver != new_receiver),
+            StatusInfo::DuplicateReceiverTokenAccount
         );
 
-        // Calculate the number of seconds not enough to generate a block.
-        let remain_seconds_temp = (u64::try_from(intervals).unwrap())
-            .checked_rem(u64::try_from(BLOCK_GEN_RATE).unwrap())
-            .unwrap();
-
-        // Calculate the number of blocks that should be generated.
-        let gen_block_num = (u64::try_from(intervals).unwrap())
-            .checked_div(u64::try_from(BLOCK_GEN_RATE).unwrap())
-            .unwrap();
-
-        // Calculate the numbers of tokens that should be minted.
-        let distribution_amounts = gen_block_num.checked_mul(COIN_NUM_PER_BLOCK).unwrap();
-
-        // Calculate the number of tokens that should be distributed to each recipient.
-        let receiver_arena_amount: u64 = ((distribution_amounts as f64) * ARENA_PERCENTAGE) as u64;
-        let receiver_nft_mining_amount: u64 =
-            ((distribution_amounts as f64) * NFT_MINING_PERCENTAGE) as u64;
-        let receiver_liquidity_mining_amount: u64 =
-            ((distribution_amounts as f64) * LIQUIDITY_MINING_PERCENTAGE) as u64;
-        let receiver_marketing_amount: u64 =
-            ((distribution_amounts as f64) * MARKETING_PERCENTAGE) as u64;
-        let receiver_ecosystem_amount: u64 =
-            ((distribution_amounts as f64) * ECOSYSTEM_PERCENTAGE) as u64;
-        let receiver_gyc_staking_amount: u64 =
-            ((distribution_amounts as f64) * GYC_STAKING_PERCENTAGE) as u64;
-
-        // Update block height.
-        let current_block_height = config_account
-            .current_block_height
-            .checked_add(gen_block_num)
-            .unwrap();
-        config_account.current_block_height = current_block_height;
-
-        // Update the timestamp of the latest block generation.
-        let last_gen_block_timestamp = current_time
-            .checked_sub(i64::try_from(remain_seconds_temp).unwrap())
-            .unwrap();
-        config_account.last_gen_block_timestamp = last_gen_block_timestamp;
-
-        // Minting tokens for each recipient.
-        ctx.accounts
-            .token_mint_to(ctx.accounts.receiver_arena.clone(), receiver_arena_amount)?;
-        ctx.accounts.token_mint_to(
-            ctx.accounts.receiver_nft_mining.clone(),
-            receiver_nft_mining_amount,
-        )?;
-        ctx.accounts.token_mint_to(
-            ctx.accounts.receiver_liquidity_mining.clone(),
-            receiver_liquidity_mining_amount,
-        )?;
-        ctx.accounts.token_mint_to(
-            ctx.accounts.receiver_marketing.clone(),
-            receiver_marketing_amount,
-        )?;
-        ctx.accounts.token_mint_to(
-            ctx.accounts.receiver_ecosystem.clone(),
-            receiver_ecosystem_amount,
-        )?;
-        ctx.accounts.token_mint_to(
-            ctx.accounts.receiver_gyc_staking.clone(),
-            receiver_gyc_staking_amount,
-        )?;
+        let bucket_info = config_account
+            .buckets_mut()
+            .get_mut(bucket as usize)
+            .ok_or(StatusInfo::InvalidBucket)?;
+        let old_receiver = bucket_info.receiver;
+        bucket_info.receiver = new_receiver;
 
-        let supply = ctx.accounts.mint.supply;
+        emit!(ReceiverChangedEvent {
+            status_code: StatusInfo::Ok as u64,
+            status_desc: "Ok".to_string(),
+            mint: config_account.mint,
+            faucet_id: config_account.faucet_id,
+            authority: config_account.authority,
+            bucket: bucket,
+            old_receiver: old_receiver,
+            new_receiver: new_receiver,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        emit!(DripEvent {
+        Ok(())
+    }
+
+    /// Accept the authority of the config proposed by the current authority.
+    /**
+     * @param ctx : AcceptAuthority context
+     */
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> ProgramResult {
+        let config_account = &mut ctx.accounts.config_account;
+        let old_authority = config_account.authority;
+        config_account.authority = config_account.pending_authority;
+        config_account.pending_authority = Pubkey::default();
+
+        emit!(AuthorityAcceptedEvent {
             status_code: StatusInfo::Ok as u64,
             status_desc: "Ok".to_string(),
-            mint: ctx.accounts.mint.to_account_info().key().clone(),
-            mint_authority: ctx.accounts.mint_authority.key().clone(),
-            receiver_arena: ctx.accounts.receiver_arena.key().clone(),
-            receiver_nft_mining: ctx.accounts.receiver_nft_mining.key().clone(),
-            receiver_liquidity_mining: ctx.accounts.receiver_liquidity_mining.key().clone(),
-            receiver_marketing: ctx.accounts.receiver_marketing.key().clone(),
-            receiver_ecosystem: ctx.accounts.receiver_ecosystem.key().clone(),
-            receiver_gyc_staking: ctx.accounts.receiver_gyc_staking.key().clone(),
-            current_block_height: current_block_height,
-            last_gen_block_timestamp: last_gen_block_timestamp,
-            receiver_arena_amount: receiver_arena_amount,
-            receiver_liquidity_mining_amount: receiver_liquidity_mining_amount,
-            receiver_nft_mining_amount: receiver_nft_mining_amount,
-            receiver_marketing_amount: receiver_marketing_amount,
-            receiver_ecosystem_amount: receiver_ecosystem_amount,
-            receiver_gyc_staking_amount: receiver_gyc_staking_amount,
-            intervals: intervals,
-            supply: supply,
+            mint: config_account.mint,
+            faucet_id: config_account.faucet_id,
+            old_authority: old_authority,
+            new_authority: config_account.authority,
             timestamp: Clock::get()?.unix_timestamp,
         });
+
+        Ok(())
+    }
+
+    /// Record tokens of the mint burned by holders, so that the audit expects them out of the supply.
+    /// The authority of any faucet of the mint can call it.
+    /**
+     * @param ctx : RecordBurn context
+     * @param amount : Amount of token burned.
+     */
+    pub fn record_burn(ctx: Context<RecordBurn>, amount: u64) -> ProgramResult {
+        require!(amount > 0, StatusInfo::InvalidParamError);
+
+        let config_account = &ctx.accounts.config_account;
+        let mint_ledger = &mut ctx.accounts.mint_ledger;
+        mint_ledger.burned_amount = mint_ledger
+            .burned_amount
+            .checked_add(amount)
+            .ok_or(StatusInfo::ArithmeticOverflow)?;
+
+        emit!(BurnRecordedEvent {
+            status_code: StatusInfo::Ok as u64,
+            status_desc: "Ok".to_string(),
+            mint: config_account.mint,
+            faucet_id: config_account.faucet_id,
+            authority: config_account.authority,
+            amount: amount,
+            burned_amount: mint_ledger.burned_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Compare the supply of the mint with what all its faucets account for, without changing anything.
+    /// The [AuditReport] is returned as return data.
+    /// This can be called by anyone.
+    /**
+     * @param ctx : Audit context
+     */
+    pub fn audit(ctx: Context<Audit>) -> ProgramResult {
+        let report = audit_supply(&ctx.accounts.mint_ledger, ctx.accounts.mint.supply)?;
+        solana_program::program::set_return_data(&report.try_to_vec()?);
+
+        emit!(AuditEvent {
+            status_code: StatusInfo::Ok as u64,
+            status_desc: "Ok".to_string(),
+            mint: ctx.accounts.mint.key(),
+            supply: report.supply,
+            expected_supply: report.expected_supply,
+            discrepancy: report.discrepancy,
+            balanced: report.balanced,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
         Ok(())
     }
 }
@@ -239,52 +769,64 @@ pub mod token_faucet {
 
 /// Accounts for Initialize.
 #[derive(Accounts)]
-#[instruction(bump: u8)]
+#[instruction(faucet_id: Option<u64>)]
 pub struct Initialize<'info> {
+    /// GYC token mint.
+    pub mint: Account<'info, Mint>,
+
     /// [config_account] of program.
     #[account(
         init,
         payer = payer,
-        seeds = [b"GameYoo-Token".as_ref()],
+        seeds = [b"GameYoo-Token".as_ref(), mint.key().as_ref(), &faucet_id.unwrap_or(0).to_le_bytes()],
         bump,
         rent_exempt = enforce
     )]
     pub config_account: Account<'info, ConfigAccount>,
 
-    /// Payer of the [config_account] initialization.
-    pub payer: Signer<'info>,
-
-    /// Token program.
-    #[account(address = token::ID @ StatusInfo::InvalidTokenProgram)]
-    pub token_program: AccountInfo<'info>,
-
-    /// GYC token mint.
-    pub mint: Account<'info, Mint>,
-
-    /// Authority who mint the token.
+    /// [emission_schedule] of [config_account].
     #[account(
-        seeds = [b"GYC-Mint-Auth".as_ref()],
-        bump = bump
+        init,
+        payer = payer,
+        seeds = [b"Emission-Schedule".as_ref(), config_account.key().as_ref()],
+        bump,
+        space = 8 + EmissionSchedule::LEN,
+        rent_exempt = enforce
     )]
-    pub mint_authority: AccountInfo<'info>,
+    pub emission_schedule: Account<'info, EmissionSchedule>,
 
-    /// The associated token account of recipient for arena.
-    pub receiver_arena: AccountInfo<'info>,
+    /// [mint_ledger] shared by the faucets of the mint, created along with the first of them.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"Mint-Ledger".as_ref(), mint.key().as_ref()],
+        bump,
+        rent_exempt = enforce
+    )]
+    pub mint_ledger: Account<'info, MintLedger>,
 
-    /// The associated token account of recipient for nft mining.
-    pub receiver_nft_mining: AccountInfo<'info>,
+    /// Payer of the [config_account] initialization.
+    /// It must be the upgrade authority of the program, so that nobody can front-run the deployment.
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(payer.key()) @StatusInfo::InvalidUpgradeAuthority
+    )]
+    pub payer: Signer<'info>,
 
-    /// The associated token account of recipient for liquidity mining.
-    pub receiver_liquidity_mining: AccountInfo<'info>,
+    /// The token faucet program itself.
+    #[account(
+        constraint = program.programdata_address() == Some(program_data.key()) @StatusInfo::InvalidProgramData
+    )]
+    pub program: Program<'info, crate::program::TokenFaucet>,
 
-    /// The associated token account of recipient for marketing.
-    pub receiver_marketing: AccountInfo<'info>,
+    /// Program data account of the token faucet program.
+    pub program_data: Account<'info, ProgramData>,
 
-    /// The associated token account of recipient for ecosystem.
-    pub receiver_ecosystem: AccountInfo<'info>,
+    /// Token program.
+    #[account(address = token::ID @ StatusInfo::InvalidTokenProgram)]
+    pub token_program: AccountInfo<'info>,
 
-    /// The associated token account of recipient for GYC staking.
-    pub receiver_gyc_staking: AccountInfo<'info>,
+    /// Authority who mint the token; its address is checked by the instruction.
+    pub mint_authority: AccountInfo<'info>,
 
     /// System program.
     #[account(address = system_program::ID)]
@@ -307,20 +849,22 @@ pub struct Drip<'info> {
     /// [config_account] of program.
     #[account(
         mut,
-        seeds = [b"GameYoo-Token".as_ref()],
-        bump,
+        seeds = [
+            b"GameYoo-Token".as_ref(),
+            config_account.mint.as_ref(),
+            &config_account.faucet_id.to_le_bytes(),
+        ],
+        bump = config_account.config_bump,
         has_one = mint @StatusInfo::InvalidTokenMint,
         has_one = mint_authority @StatusInfo::InvalidTokenAuthority,
-        has_one = receiver_arena @StatusInfo::InvalidReceiverTokenAccount,
-        has_one = receiver_nft_mining @StatusInfo::InvalidReceiverTokenAccount,
-        has_one = receiver_liquidity_mining @StatusInfo::InvalidReceiverTokenAccount,
-        has_one = receiver_marketing @StatusInfo::InvalidReceiverTokenAccount,
-        has_one = receiver_ecosystem @StatusInfo::InvalidReceiverTokenAccount,
-        has_one = receiver_gyc_staking @StatusInfo::InvalidReceiverTokenAccount,
+        has_one = emission_schedule @StatusInfo::InvalidEmissionSchedule,
         constraint = config_account.magic == 0x544b4654 @StatusInfo::InvalidMagic,
     )]
     pub config_account: Account<'info, ConfigAccount>,
 
+    /// [emission_schedule] of [config_account].
+    pub emission_schedule: Account<'info, EmissionSchedule>,
+
     /// Token program.
     #[account(address = token::ID @ StatusInfo::InvalidTokenProgram)]
     pub token_program: AccountInfo<'info>,
@@ -329,40 +873,227 @@ pub struct Drip<'info> {
     #[account(mut)]
     pub mint: Account<'info, Mint>,
 
+    /// [mint_ledger] shared by the faucets of the mint.
+    #[account(
+        mut,
+        seeds = [b"Mint-Ledger".as_ref(), mint.key().as_ref()],
+        bump = mint_ledger.bump
+    )]
+    pub mint_ledger: Account<'info, MintLedger>,
+
     /// Authority who mint the token.
     #[account(
-        seeds = [b"GYC-Mint-Auth".as_ref()],
-        bump = config_account.bump
+        seeds = [b"GYC-Mint-Auth".as_ref(), mint.key().as_ref()],
+        bump = config_account.mint_authority_bump
     )]
     pub mint_authority: AccountInfo<'info>,
 
-    /// The associated token account of recipient for arena.
+    /// The token account of the caller, receiving the cranker reward.
     #[account(mut)]
-    pub receiver_arena: AccountInfo<'info>,
+    pub cranker: AccountInfo<'info>,
 
-    /// The associated token account of recipient for nft mining.
-    #[account(mut)]
-    pub receiver_nft_mining: AccountInfo<'info>,
+    /// Clock represents network time.
+    #[account(address = solana_program::sysvar::clock::ID @ StatusInfo::InvalidSysvarClock)]
+    pub clock: Sysvar<'info, Clock>,
+}
 
-    /// The associated token account of recipient for liquidity mining.
-    #[account(mut)]
-    pub receiver_liquidity_mining: AccountInfo<'info>,
+/* PreviewDrip context */
 
-    /// The associated token account of recipient for marketing.
-    #[account(mut)]
-    pub receiver_marketing: AccountInfo<'info>,
+/// Accounts for PreviewDrip.
+#[derive(Accounts)]
+pub struct PreviewDrip<'info> {
+    /// [config_account] of program.
+    #[account(
+        seeds = [
+            b"GameYoo-Token".as_ref(),
+            config_account.mint.as_ref(),
+            &config_account.faucet_id.to_le_bytes(),
+        ],
+        bump = config_account.config_bump,
+        has_one = mint @StatusInfo::InvalidTokenMint,
+        has_one = emission_schedule @StatusInfo::InvalidEmissionSchedule,
+        constraint = config_account.magic == 0x544b4654 @StatusInfo::InvalidMagic,
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+
+    /// [emission_schedule] of [config_account].
+    pub emission_schedule: Account<'info, EmissionSchedule>,
+
+    /// GYC token mint.
+    pub mint: Account<'info, Mint>,
+
+    /// [mint_ledger] shared by the faucets of the mint.
+    #[account(
+        seeds = [b"Mint-Ledger".as_ref(), mint.key().as_ref()],
+        bump = mint_ledger.bump
+    )]
+    pub mint_ledger: Account<'info, MintLedger>,
+}
 
-    /// The associated token account of recipient for ecosystem.
+/* ClaimBucket context */
+
+/// Accounts for ClaimBucket.
+#[derive(Accounts)]
+pub struct ClaimBucket<'info> {
+    /// [config_account] of program.
+    #[account(
+        mut,
+        seeds = [
+            b"GameYoo-Token".as_ref(),
+            config_account.mint.as_ref(),
+            &config_account.faucet_id.to_le_bytes(),
+        ],
+        bump = config_account.config_bump,
+        has_one = mint @StatusInfo::InvalidTokenMint,
+        has_one = mint_authority @StatusInfo::InvalidTokenAuthority,
+        constraint = config_account.magic == 0x544b4654 @StatusInfo::InvalidMagic,
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+
+    /// Token program.
+    #[account(address = token::ID @ StatusInfo::InvalidTokenProgram)]
+    pub token_program: AccountInfo<'info>,
+
+    /// GYC token mint.
     #[account(mut)]
-    pub receiver_ecosystem: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+
+    /// [mint_ledger] shared by the faucets of the mint.
+    #[account(
+        mut,
+        seeds = [b"Mint-Ledger".as_ref(), mint.key().as_ref()],
+        bump = mint_ledger.bump
+    )]
+    pub mint_ledger: Account<'info, MintLedger>,
 
-    /// The associated token account of recipient for GYC staking.
+    /// Authority who mint the token.
+    #[account(
+        seeds = [b"GYC-Mint-Auth".as_ref(), mint.key().as_ref()],
+        bump = config_account.mint_authority_bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The associated token account of recipient of the bucket.
     #[account(mut)]
-    pub receiver_gyc_staking: AccountInfo<'info>,
+    pub receiver: AccountInfo<'info>,
+}
 
-    /// Clock represents network time.
-    #[account(address = solana_program::sysvar::clock::ID @ StatusInfo::InvalidSysvarClock)]
-    pub clock: Sysvar<'info, Clock>,
+/* UpdateConfig context */
+
+/// Accounts for the instructions by which the authority updates the config.
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// [config_account] of program.
+    #[account(
+        mut,
+        seeds = [
+            b"GameYoo-Token".as_ref(),
+            config_account.mint.as_ref(),
+            &config_account.faucet_id.to_le_bytes(),
+        ],
+        bump = config_account.config_bump,
+        has_one = authority @StatusInfo::InvalidAuthority,
+        constraint = config_account.magic == 0x544b4654 @StatusInfo::InvalidMagic,
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+
+    /// The authority of [config_account].
+    pub authority: Signer<'info>,
+}
+
+/* SetReceiver context */
+
+/// Accounts for SetReceiver.
+#[derive(Accounts)]
+pub struct SetReceiver<'info> {
+    /// [config_account] of program.
+    #[account(
+        mut,
+        seeds = [
+            b"GameYoo-Token".as_ref(),
+            config_account.mint.as_ref(),
+            &config_account.faucet_id.to_le_bytes(),
+        ],
+        bump = config_account.config_bump,
+        has_one = authority @StatusInfo::InvalidAuthority,
+        constraint = config_account.magic == 0x544b4654 @StatusInfo::InvalidMagic,
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+
+    /// The authority of [config_account].
+    pub authority: Signer<'info>,
+
+    /// The new associated token account of recipient.
+    pub new_receiver: AccountInfo<'info>,
+}
+
+/* RecordBurn context */
+
+/// Accounts for RecordBurn.
+#[derive(Accounts)]
+pub struct RecordBurn<'info> {
+    /// [config_account] of program.
+    #[account(
+        seeds = [
+            b"GameYoo-Token".as_ref(),
+            config_account.mint.as_ref(),
+            &config_account.faucet_id.to_le_bytes(),
+        ],
+        bump = config_account.config_bump,
+        has_one = authority @StatusInfo::InvalidAuthority,
+        constraint = config_account.magic == 0x544b4654 @StatusInfo::InvalidMagic,
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+
+    /// The authority of [config_account].
+    pub authority: Signer<'info>,
+
+    /// [mint_ledger] shared by the faucets of the mint.
+    #[account(
+        mut,
+        seeds = [b"Mint-Ledger".as_ref(), config_account.mint.as_ref()],
+        bump = mint_ledger.bump
+    )]
+    pub mint_ledger: Account<'info, MintLedger>,
+}
+
+/* Audit context */
+
+/// Accounts for Audit.
+#[derive(Accounts)]
+pub struct Audit<'info> {
+    /// GYC token mint.
+    pub mint: Account<'info, Mint>,
+
+    /// [mint_ledger] shared by the faucets of the mint.
+    #[account(
+        seeds = [b"Mint-Ledger".as_ref(), mint.key().as_ref()],
+        bump = mint_ledger.bump
+    )]
+    pub mint_ledger: Account<'info, MintLedger>,
+}
+
+/* AcceptAuthority context */
+
+/// Accounts for AcceptAuthority.
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    /// [config_account] of program.
+    #[account(
+        mut,
+        seeds = [
+            b"GameYoo-Token".as_ref(),
+            config_account.mint.as_ref(),
+            &config_account.faucet_id.to_le_bytes(),
+        ],
+        bump = config_account.config_bump,
+        has_one = pending_authority @StatusInfo::InvalidPendingAuthority,
+        constraint = config_account.magic == 0x544b4654 @StatusInfo::InvalidMagic,
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+
+    /// The authority proposed by the current authority of [config_account].
+    pub pending_authority: Signer<'info>,
 }
 
 // --------------------------------
@@ -374,32 +1105,78 @@ pub struct Drip<'info> {
 pub struct ConfigAccount {
     /// The magic number for current program.
     pub magic: u32,
-    /// Bump seed for program address.
-    pub bump: u8,
+    /// Id telling apart the faucets of the same mint.
+    pub faucet_id: u64,
+    /// Bump seed for the address of the config account.
+    pub config_bump: u8,
+    /// Bump seed for the address of the mint authority.
+    pub mint_authority_bump: u8,
+    /// Whether the max supply has been reached and emission is over.
+    pub finished: bool,
+    /// Whether drip is paused by the authority.
+    pub paused: bool,
+    /// When drip was paused.
+    pub paused_timestamp: i64,
+    /// Slot at which drip was paused.
+    pub paused_slot: u64,
 
     /// The current block height.
     pub current_block_height: u64,
 
-    /// The timestamp of the last block generation.
+    /// Clock source counting the blocks.
+    pub clock_source: ClockSource,
+    /// Seconds or slots, depending on [clock_source], between two generated blocks.
+    pub block_gen_rate: u32,
+    /// Max number of blocks generated by a single drip.
+    pub max_blocks_per_drip: u64,
+    /// Whether the pending blocks older than [grace_blocks] are forfeited instead of caught up.
+    pub forfeit_missed_blocks: bool,
+    /// Number of the latest pending blocks kept in forfeit mode.
+    pub grace_blocks: u64,
+    /// Total number of blocks forfeited.
+    pub forfeited_block_num: u64,
+    /// Total amount of token forfeited.
+    pub forfeited_amount: u64,
+    /// Share of each drip rewarded to its caller, in basis points.
+    pub cranker_reward_bps: u16,
+    /// Max reward of the caller of a single drip.
+    pub max_cranker_reward: u64,
+    /// Lifetime tokens emitted by the faucet, to the buckets and the callers of drip.
+    pub total_emitted: u64,
+    /// Emission schedule deciding the reward of each block.
+    pub emission_schedule: Pubkey,
+    /// The era of the next block, i.e. its segment in the emission schedule.
+    pub current_era: u8,
+    /// The reward of the next block.
+    pub current_reward: u64,
+
+    /// The timestamp of the last block generation; with the slot clock source, of the last drip.
     pub last_gen_block_timestamp: i64,
+    /// The slot of the last block generation; with the unix timestamp clock source, of the last drip.
+    pub last_gen_block_slot: u64,
+    /// When emission starts, with the unix timestamp clock source.
+    pub start_timestamp: i64,
+    /// When emission ends, or 0 if it never ends, with the unix timestamp clock source.
+    pub end_timestamp: i64,
+    /// Slot at which emission starts, with the slot clock source.
+    pub start_slot: u64,
+    /// Slot at which emission ends, or 0 if it never ends, with the slot clock source.
+    pub end_slot: u64,
 
     /// Mint for GYC.
     pub mint: Pubkey,
     /// Authority who mint the token.
     pub mint_authority: Pubkey,
 
-    /// The associated token account of recipient for arena.
-    pub receiver_arena: Pubkey,
-    /// The associated token account of recipient for nft mining.
-    pub receiver_nft_mining: Pubkey,
-    /// The associated token account of recipient for liquidity mining.
-    pub receiver_liquidity_mining: Pubkey,
-    /// The associated token account of recipient for marketing.
-    pub receiver_marketing: Pubkey,
-    /// The associated token account of recipient for ecosystem.
-    pub receiver_ecosystem: Pubkey,
-    /// The associated token account of recipient for GYC staking.
-    pub receiver_gyc_staking: Pubkey,
+    /// Admin authority allowed to change the config.
+    pub authority: Pubkey,
+    /// Authority proposed to take over, or the default pubkey if none.
+    pub pending_authority: Pubkey,
+
+    /// Number of distribution buckets in use.
+    pub bucket_count: u8,
+    /// Distribution buckets, only the first [bucket_count] are in use.
+    pub buckets: [Bucket; MAX_BUCKETS],
 }
 
 impl Default for ConfigAccount {
@@ -408,25 +1185,408 @@ impl Default for ConfigAccount {
     }
 }
 
-impl<'info> Drip<'info> {
-    /// Minting tokens for recipient.
-    fn token_mint_to(&mut self, receiver: AccountInfo<'info>, amount: u64) -> ProgramResult {
-        let cpi_program = self.token_program.clone();
-        let cpi_accounts = token::MintTo {
-            authority: self.mint_authority.to_account_info(),
-            mint: self.mint.to_account_info(),
-            to: receiver.to_account_info(),
-        };
+impl ConfigAccount {
+    /// The distribution buckets in use.
+    pub fn buckets(&self) -> &[Bucket] {
+        &self.buckets[..self.bucket_count as usize]
+    }
 
-        let seeds = &[b"GYC-Mint-Auth".as_ref(), &[self.config_account.bump]];
-        let signer_seeds = &[&seeds[..]];
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::mint_to(cpi_ctx, amount)?;
+    /// The distribution buckets in use, mutable.
+    pub fn buckets_mut(&mut self) -> &mut [Bucket] {
+        &mut self.buckets[..self.bucket_count as usize]
+    }
 
-        Ok(())
+    /// Emission state of the faucet following `segments`, given the `supply` of the mint
+    /// including the accruals of all its faucets not yet claimed.
+    pub fn emission_state(
+        &self,
+        segments: &[EmissionSegment],
+        supply: u64,
+    ) -> Result<EmissionState> {
+        Ok(EmissionState {
+            segments: segments
+                .iter()
+                .map(|segment| emission::Segment::from(*segment))
+                .collect(),
+            weights: self.buckets().iter().map(|bucket| bucket.weight).collect(),
+            remainders: self
+                .buckets()
+                .iter()
+                .map(|bucket| bucket.remainder)
+                .collect(),
+            supply: supply,
+            max_supply: MAX_TOTAL_SUPPLY,
+            finished: self.finished,
+            paused: self.paused,
+            clock_source: self.clock_source.into(),
+            block_gen_rate: self.block_gen_rate,
+            max_blocks_per_drip: self.max_blocks_per_drip,
+            forfeit_missed_blocks: self.forfeit_missed_blocks,
+            grace_blocks: self.grace_blocks,
+            cranker_reward_bps: self.cranker_reward_bps,
+            max_cranker_reward: self.max_cranker_reward,
+            current_block_height: self.current_block_height,
+            last_gen_block_timestamp: self.last_gen_block_timestamp,
+            last_gen_block_slot: self.last_gen_block_slot,
+            start_timestamp: self.start_timestamp,
+            end_timestamp: self.end_timestamp,
+            start_slot: self.start_slot,
+            end_slot: self.end_slot,
+        })
+    }
+}
+
+/// A distribution bucket accruing a share of each drip.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Bucket {
+    /// The associated token account of recipient.
+    pub receiver: Pubkey,
+    /// Distribution weight; the bucket receives weight / total weight of each drip.
+    pub weight: u64,
+    /// Rounding dust not yet distributed, in units of 1 / total weight lamport.
+    pub remainder: u64,
+    /// Tokens accrued to the bucket and not yet claimed by its recipient.
+    pub accrued: u64,
+    /// Lifetime tokens accrued to the bucket by drip, whether claimed yet or not.
+    pub total_accrued: u64,
+}
+
+/// Emission schedule of a config, a piecewise constant block reward.
+#[account]
+#[derive(Default)]
+pub struct EmissionSchedule {
+    /// The config account which the schedule belongs to.
+    pub config_account: Pubkey,
+    /// Segments sorted by start block height, the first one starting at height 0.
+    /// The last segment lasts forever; a segment rewarding 0 stops emission.
+    pub segments: Vec<EmissionSegment>,
+}
+
+impl EmissionSchedule {
+    /// Account size with the max number of segments, without discriminator.
+    pub const LEN: usize = 32 + 4 + MAX_EMISSION_SEGMENTS * EmissionSegment::LEN;
+}
+
+/// A segment of the emission schedule.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct EmissionSegment {
+    /// Block height at which the segment starts.
+    pub start_block_height: u64,
+    /// Tokens generated by each block of the segment.
+    pub reward_per_block: u64,
+}
+
+impl EmissionSegment {
+    /// Serialized size of a segment.
+    pub const LEN: usize = 8 + 8;
+}
+
+impl From<EmissionSegment> for emission::Segment {
+    fn from(segment: EmissionSegment) -> emission::Segment {
+        emission::Segment {
+            start_block_height: segment.start_block_height,
+            reward_per_block: segment.reward_per_block,
+        }
     }
 }
 
+/// Ledger shared by the faucets of a mint, so that together they stay below [MAX_TOTAL_SUPPLY].
+#[account]
+#[derive(Default)]
+pub struct MintLedger {
+    /// Mint for GYC.
+    pub mint: Pubkey,
+    /// Bump seed for the address of the ledger.
+    pub bump: u8,
+    /// Supply of the mint before its first faucet was initialized.
+    pub pre_mint_supply: u64,
+    /// Lifetime tokens emitted by all the faucets of the mint, to the buckets and the callers of drip.
+    pub total_emitted: u64,
+    /// Tokens accrued to the buckets of all the faucets of the mint and not yet claimed.
+    pub outstanding: u64,
+    /// Tokens recorded by the authorities of the faucets as burned by holders.
+    pub burned_amount: u64,
+}
+
+impl MintLedger {
+    /// Supply of the mint given its `mint_supply`, including the accruals not yet claimed.
+    pub fn supply(&self, mint_supply: u64) -> Result<u64> {
+        mint_supply
+            .checked_add(self.outstanding)
+            .ok_or_else(|| StatusInfo::ArithmeticOverflow.into())
+    }
+}
+
+/// Supply reconciliation of a mint, returned by the audit instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct AuditReport {
+    /// Supply of the mint.
+    pub supply: u64,
+    /// Supply of the mint before its first faucet was initialized.
+    pub pre_mint_supply: u64,
+    /// Lifetime tokens emitted by all the faucets of the mint.
+    pub total_emitted: u64,
+    /// Tokens accrued to the buckets and not yet minted.
+    pub outstanding: u64,
+    /// Tokens recorded as burned by holders.
+    pub burned_amount: u64,
+    /// Supply the faucets account for: pre-mint + emitted - outstanding - burned.
+    pub expected_supply: i128,
+    /// Supply minus the expected supply.
+    pub discrepancy: i128,
+    /// Whether the supply matches the expected supply.
+    pub balanced: bool,
+}
+
+/// Clock counting the blocks of a faucet.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum ClockSource {
+    /// Blocks are generated every [block_gen_rate] seconds of `Clock::unix_timestamp`.
+    UnixTimestamp,
+    /// Blocks are generated every [block_gen_rate] slots of `Clock::slot`.
+    Slot,
+}
+
+impl From<ClockSource> for emission::ClockSource {
+    fn from(clock_source: ClockSource) -> emission::ClockSource {
+        match clock_source {
+            ClockSource::UnixTimestamp => emission::ClockSource::UnixTimestamp,
+            ClockSource::Slot => emission::ClockSource::Slot,
+        }
+    }
+}
+
+/// What a drip does, returned by the preview_drip instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct DripPlan {
+    /// interval since the last block generation, in seconds or slots depending on the clock source.
+    pub intervals: i64,
+    /// Number of blocks forfeited.
+    pub forfeited_block_num: u64,
+    /// Amount of token forfeited.
+    pub forfeited_amount: u64,
+    /// Number of blocks generated.
+    pub gen_block_num: u64,
+    /// Number of blocks pending but not generated, left to the next drips.
+    pub backlog_block_num: u64,
+    /// The block height after the drip.
+    pub current_block_height: u64,
+    /// The era of the next block.
+    pub current_era: u8,
+    /// The reward of the next block.
+    pub current_reward: u64,
+    /// The timestamp of last block generation after the drip.
+    pub last_gen_block_timestamp: i64,
+    /// The slot of last block generation after the drip.
+    pub last_gen_block_slot: u64,
+    /// Whether the drip reaches [MAX_TOTAL_SUPPLY] and ends emission.
+    pub cap_reached: bool,
+    /// Amount of token minted to the caller.
+    pub cranker_reward: u64,
+    /// Amount of token accrued to each bucket.
+    pub amounts: Vec<u64>,
+    /// Rounding dust of each bucket after the drip.
+    pub remainders: Vec<u64>,
+    /// Supply after the drip, including the accruals not yet claimed.
+    pub supply: u64,
+}
+
+impl From<emission::DripPlan> for DripPlan {
+    fn from(plan: emission::DripPlan) -> DripPlan {
+        DripPlan {
+            intervals: plan.intervals,
+            forfeited_block_num: plan.forfeited_block_num,
+            forfeited_amount: plan.forfeited_amount,
+            gen_block_num: plan.gen_block_num,
+            backlog_block_num: plan.backlog_block_num,
+            current_block_height: plan.current_block_height,
+            current_era: plan.current_era,
+            current_reward: plan.current_reward,
+            last_gen_block_timestamp: plan.last_gen_block_timestamp,
+            last_gen_block_slot: plan.last_gen_block_slot,
+            cap_reached: plan.cap_reached,
+            cranker_reward: plan.cranker_reward,
+            amounts: plan.amounts,
+            remainders: plan.remainders,
+            supply: plan.supply,
+        }
+    }
+}
+
+/// Settings of a faucet given at initialization.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeParams {
+    /// Clock source counting the blocks.
+    pub clock_source: ClockSource,
+    /// Seconds or slots, depending on [clock_source], between two generated blocks.
+    pub block_gen_rate: u32,
+    /// Max number of blocks generated by a single drip.
+    pub max_blocks_per_drip: u64,
+    /// Number of the latest pending blocks kept by a drip, the older ones being forfeited;
+    /// `None` catches up all pending blocks.
+    pub grace_blocks: Option<u64>,
+    /// Share of each drip rewarded to its caller, in basis points.
+    pub cranker_reward_bps: u16,
+    /// Max reward of the caller of a single drip.
+    pub max_cranker_reward: u64,
+    /// When emission starts with the unix timestamp clock source, not in the past; `None` starts it at once.
+    pub start_timestamp: Option<i64>,
+    /// When emission ends with the unix timestamp clock source, after the start; `None` never ends it.
+    pub end_timestamp: Option<i64>,
+    /// Slot at which emission starts with the slot clock source, not in the past; `None` starts it at once.
+    pub start_slot: Option<u64>,
+    /// Slot at which emission ends with the slot clock source, after the start; `None` never ends it.
+    pub end_slot: Option<u64>,
+    /// Emission schedule, given as segments or as a halving schedule.
+    pub schedule: ScheduleParams,
+    /// Distribution weight of each bucket.
+    pub weights: Vec<u64>,
+    /// Whether the freeze authority of the mint must be unset or the mint authority.
+    pub check_freeze_authority: bool,
+}
+
+/// Emission schedule of a faucet given at initialization.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum ScheduleParams {
+    /// Segments sorted by start block height, the first one starting at height 0.
+    Segments { segments: Vec<EmissionSegment> },
+    /// A block reward halved every `halving_interval` blocks.
+    Halving {
+        /// Number of blocks after which the block reward is halved.
+        halving_interval: u64,
+        /// Reward of the blocks of the first era.
+        initial_reward: u64,
+        /// Number of eras with a block reward; no tokens are generated afterwards.
+        halving_eras: u8,
+    },
+}
+
+impl ScheduleParams {
+    /// The segments of the emission schedule; a halving schedule has a segment per era
+    /// and a last segment rewarding 0.
+    pub fn segments(self) -> Result<Vec<EmissionSegment>> {
+        match self {
+            ScheduleParams::Segments { segments } => Ok(segments),
+            ScheduleParams::Halving {
+                halving_interval,
+                initial_reward,
+                halving_eras,
+            } => {
+                require!(halving_interval > 0, StatusInfo::InvalidEmissionSchedule);
+                require!(
+                    halving_eras > 0 && (halving_eras as usize) < MAX_EMISSION_SEGMENTS,
+                    StatusInfo::InvalidEmissionSchedule
+                );
+
+                (0..=halving_eras)
+                    .map(|era| {
+                        Ok(EmissionSegment {
+                            start_block_height: halving_interval
+                                .checked_mul(u64::from(era))
+                                .ok_or(StatusInfo::InvalidEmissionSchedule)?,
+                            reward_per_block: if era < halving_eras {
+                                initial_reward >> era
+                            } else {
+                                0
+                            },
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Minting tokens for recipient, signed by the mint authority of [config_account].
+fn token_mint_to<'info>(
+    config_account: &ConfigAccount,
+    token_program: &AccountInfo<'info>,
+    mint: &Account<'info, Mint>,
+    mint_authority: &AccountInfo<'info>,
+    receiver: &AccountInfo<'info>,
+    amount: u64,
+) -> ProgramResult {
+    let cpi_program = token_program.clone();
+    let cpi_accounts = token::MintTo {
+        authority: mint_authority.clone(),
+        mint: mint.to_account_info(),
+        to: receiver.clone(),
+    };
+
+    let seeds = &[
+        b"GYC-Mint-Auth".as_ref(),
+        config_account.mint.as_ref(),
+        &[config_account.mint_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token::mint_to(cpi_ctx, amount)?;
+
+    Ok(())
+}
+
+/// Check that `receiver` is an initialized token account of `mint` which is not frozen.
+pub fn validate_receiver(receiver: &AccountInfo, mint: &Pubkey) -> ProgramResult {
+    require!(
+        !receiver.data_is_empty(),
+        StatusInfo::NotInitializedAssociatedTokenAccount
+    );
+    require!(
+        *receiver.owner == token::ID,
+        StatusInfo::InvalidReceiverOwner
+    );
+
+    let token_account = spl_token::state::Account::unpack_unchecked(&receiver.try_borrow_data()?)
+        .map_err(|_| StatusInfo::InvalidReceiverTokenAccount)?;
+    require!(
+        token_account.is_initialized(),
+        StatusInfo::NotInitializedAssociatedTokenAccount
+    );
+    require!(token_account.mint == *mint, StatusInfo::InvalidReceiverMint);
+    require!(
+        !token_account.is_frozen(),
+        StatusInfo::FrozenReceiverTokenAccount
+    );
+
+    Ok(())
+}
+
+/// Check that no associated token account receives for two buckets.
+pub fn require_distinct_receivers(receivers: &[Pubkey]) -> ProgramResult {
+    for (i, receiver) in receivers.iter().enumerate() {
+        require!(
+            !receivers[i + 1..].contains(receiver),
+            StatusInfo::DuplicateReceiverTokenAccount
+        );
+    }
+
+    Ok(())
+}
+
+/// Reconcile the `supply` of the mint with what the faucets of `mint_ledger` account for.
+/// Tokens minted by anyone else, or burned and not recorded, show up as a discrepancy.
+pub fn audit_supply(mint_ledger: &MintLedger, supply: u64) -> Result<AuditReport> {
+    let expected_supply = (mint_ledger.pre_mint_supply as i128)
+        .checked_add(mint_ledger.total_emitted as i128)
+        .and_then(|sum| sum.checked_sub(mint_ledger.outstanding as i128))
+        .and_then(|sum| sum.checked_sub(mint_ledger.burned_amount as i128))
+        .ok_or(StatusInfo::ArithmeticOverflow)?;
+    let discrepancy = (supply as i128)
+        .checked_sub(expected_supply)
+        .ok_or(StatusInfo::ArithmeticOverflow)?;
+
+    Ok(AuditReport {
+        supply: supply,
+        pre_mint_supply: mint_ledger.pre_mint_supply,
+        total_emitted: mint_ledger.total_emitted,
+        outstanding: mint_ledger.outstanding,
+        burned_amount: mint_ledger.burned_amount,
+        expected_supply: expected_supply,
+        discrepancy: discrepancy,
+        balanced: discrepancy == 0,
+    })
+}
+
 ///-------------------------------------
 /// Events
 ///-------------------------------------
@@ -441,38 +1601,104 @@ pub struct InitializeEvent {
     /// [Mint] of [GYC] Token.
     #[index]
     pub mint: Pubkey,
+    /// Id of the faucet of [mint].
+    pub faucet_id: u64,
     /// Authority mint.
     #[index]
     pub mint_authority: Pubkey,
-    /// The associated token account of recipient for arena.
-    #[index]
-    pub receiver_arena: Pubkey,
-    /// The associated token account of recipient for nft mining.
+    /// Admin authority of the config.
     #[index]
-    pub receiver_nft_mining: Pubkey,
-    /// The associated token account of recipient for liquidity mining.
-    #[index]
-    pub receiver_liquidity_mining: Pubkey,
-    /// The associated token account of recipient for marketing.
+    pub authority: Pubkey,
+    /// The associated token accounts of recipient, one per bucket.
+    pub receivers: Vec<Pubkey>,
+    /// Distribution weight of each bucket.
+    pub weights: Vec<u64>,
+    /// The current block height.
+    pub current_block_height: u64,
+    /// The timestamp of last block generation.
+    pub last_gen_block_timestamp: i64,
+    /// When the event took place.
+    pub timestamp: i64,
+}
+
+/// Triggered when new tokens are accrued by a simulated follow block generation.
+#[event]
+pub struct DripEvent {
+    /// Status code
+    pub status_code: u64,
+    /// Status description info.
+    pub status_desc: String,
+    /// [Mint] of [GYC] Token.
     #[index]
-    pub receiver_marketing: Pubkey,
-    /// The associated token account of recipient for ecosystem.
+    pub mint: Pubkey,
+    /// Id of the faucet of [mint].
+    pub faucet_id: u64,
+    /// The current block height.
+    pub current_block_height: u64,
+    /// The era of the next block.
+    pub current_era: u8,
+    /// The reward of the next block.
+    pub current_reward: u64,
+    /// The timestamp of last block generation.
+    pub last_gen_block_timestamp: i64,
+    /// The slot of last block generation.
+    pub last_gen_block_slot: u64,
+    /// Amount of token accrued to each bucket.
+    pub amounts: Vec<u64>,
+    /// The token account of the caller, receiving the cranker reward.
     #[index]
-    pub receiver_ecosystem: Pubkey,
-    /// The associated token account of recipient for GYC staking.
+    pub cranker: Pubkey,
+    /// Amount of token minted to the caller.
+    pub cranker_reward: u64,
+    /// Lifetime amount of token accrued to each bucket.
+    pub bucket_totals: Vec<u64>,
+    /// Lifetime amount of token emitted by the faucet.
+    pub total_emitted: u64,
+    /// interval of invoke drip instruction, in seconds or slots depending on the clock source.
+    pub intervals: i64,
+    /// Number of blocks pending but not generated by this drip, left to the next drips.
+    pub backlog_block_num: u64,
+    /// Number of blocks forfeited by this drip.
+    pub forfeited_block_num: u64,
+    /// Amount of token forfeited by this drip.
+    pub forfeited_amount: u64,
+    /// Total number of blocks forfeited.
+    pub total_forfeited_block_num: u64,
+    /// Total amount of token forfeited.
+    pub total_forfeited_amount: u64,
+    /// Latest supply, including the accruals not yet claimed.
+    pub supply: u64,
+    /// When the event took place.
+    pub timestamp: i64,
+}
+
+/// Triggered when the last drip accrues the supply up to [MAX_TOTAL_SUPPLY].
+#[event]
+pub struct CapReachedEvent {
+    /// Status code
+    pub status_code: u64,
+    /// Status description info.
+    pub status_desc: String,
+    /// [Mint] of [GYC] Token.
     #[index]
-    pub receiver_gyc_staking: Pubkey,
+    pub mint: Pubkey,
+    /// Id of the faucet of [mint].
+    pub faucet_id: u64,
     /// The current block height.
     pub current_block_height: u64,
     /// The timestamp of last block generation.
     pub last_gen_block_timestamp: i64,
+    /// Amount of token accrued and rewarded by the last drip.
+    pub final_amounts: u64,
+    /// Latest supply, including the accruals not yet claimed.
+    pub supply: u64,
     /// When the event took place.
     pub timestamp: i64,
 }
 
-/// Triggered when new tokens are minted by a simulated follow block generation.
+/// Triggered when a bucket claims its accrual.
 #[event]
-pub struct DripEvent {
+pub struct ClaimEvent {
     /// Status code
     pub status_code: u64,
     /// Status description info.
@@ -480,47 +1706,158 @@ pub struct DripEvent {
     /// [Mint] of [GYC] Token.
     #[index]
     pub mint: Pubkey,
-    /// Authority mint.
+    /// Id of the faucet of [mint].
+    pub faucet_id: u64,
+    /// Index of the bucket.
+    pub bucket: u8,
+    /// The associated token account of recipient of the bucket.
     #[index]
-    pub mint_authority: Pubkey,
-    /// The associated token account of recipient for arena.
+    pub receiver: Pubkey,
+    /// Amount of token minted to the recipient.
+    pub amount: u64,
+    /// Latest supply of [mint].
+    pub supply: u64,
+    /// When the event took place.
+    pub timestamp: i64,
+}
+
+/// Triggered when a new authority of the config is proposed.
+#[event]
+pub struct AuthorityProposedEvent {
+    /// Status code
+    pub status_code: u64,
+    /// Status description info.
+    pub status_desc: String,
+    /// [Mint] of [GYC] Token.
+    #[index]
+    pub mint: Pubkey,
+    /// Id of the faucet of [mint].
+    pub faucet_id: u64,
+    /// The current authority.
+    #[index]
+    pub authority: Pubkey,
+    /// The proposed authority.
+    #[index]
+    pub pending_authority: Pubkey,
+    /// When the event took place.
+    pub timestamp: i64,
+}
+
+/// Triggered when the proposed authority of the config takes over.
+#[event]
+pub struct AuthorityAcceptedEvent {
+    /// Status code
+    pub status_code: u64,
+    /// Status description info.
+    pub status_desc: String,
+    /// [Mint] of [GYC] Token.
     #[index]
-    pub receiver_arena: Pubkey,
-    /// The associated token account of recipient for nft mining.
+    pub mint: Pubkey,
+    /// Id of the faucet of [mint].
+    pub faucet_id: u64,
+    /// The previous authority.
     #[index]
-    pub receiver_nft_mining: Pubkey,
-    /// The associated token account of recipient for liquidity mining.
+    pub old_authority: Pubkey,
+    /// The new authority.
     #[index]
-    pub receiver_liquidity_mining: Pubkey,
-    /// The associated token account of recipient for marketing.
+    pub new_authority: Pubkey,
+    /// When the event took place.
+    pub timestamp: i64,
+}
+
+/// Triggered when drip is paused or unpaused.
+#[event]
+pub struct PauseEvent {
+    /// Status code
+    pub status_code: u64,
+    /// Status description info.
+    pub status_desc: String,
+    /// [Mint] of [GYC] Token.
     #[index]
-    pub receiver_marketing: Pubkey,
-    /// The associated token account of recipient for ecosystem.
+    pub mint: Pubkey,
+    /// Id of the faucet of [mint].
+    pub faucet_id: u64,
+    /// The authority of the config.
     #[index]
-    pub receiver_ecosystem: Pubkey,
-    /// The associated token account of recipient for GYC staking.
+    pub authority: Pubkey,
+    /// Whether drip is paused now.
+    pub paused: bool,
+    /// Seconds elapsed while paused, for which no blocks are generated; 0 with the slot clock source.
+    pub forfeited_seconds: i64,
+    /// Slots elapsed while paused, for which no blocks are generated; 0 with the unix timestamp clock source.
+    pub forfeited_slots: u64,
+    /// When the event took place.
+    pub timestamp: i64,
+}
+
+/// Triggered when the recipient of a bucket is replaced.
+#[event]
+pub struct ReceiverChangedEvent {
+    /// Status code
+    pub status_code: u64,
+    /// Status description info.
+    pub status_desc: String,
+    /// [Mint] of [GYC] Token.
     #[index]
-    pub receiver_gyc_staking: Pubkey,
-    /// The current block height.
-    pub current_block_height: u64,
-    /// The timestamp of last block generation.
-    pub last_gen_block_timestamp: i64,
-    /// Amount of token distribution to arena.
-    pub receiver_arena_amount: u64,
-    /// Amount of token distribution to nft mining.
-    pub receiver_nft_mining_amount: u64,
-    /// Amount of token distribution to liquidity mining.
-    pub receiver_liquidity_mining_amount: u64,
-    /// Amount of token distribution to marketing.
-    pub receiver_marketing_amount: u64,
-    /// Amount of token distribution to ecosystem.
-    pub receiver_ecosystem_amount: u64,
-    /// Amount of token distribution to GYC staking.
-    pub receiver_gyc_staking_amount: u64,
-    /// interval of invoke drip instruction.
-    pub intervals: i64,
-    /// Latest supply.
+    pub mint: Pubkey,
+    /// Id of the faucet of [mint].
+    pub faucet_id: u64,
+    /// The authority of the config.
+    #[index]
+    pub authority: Pubkey,
+    /// Index of the bucket.
+    pub bucket: u8,
+    /// The previous associated token account of recipient.
+    #[index]
+    pub old_receiver: Pubkey,
+    /// The new associated token account of recipient.
+    #[index]
+    pub new_receiver: Pubkey,
+    /// When the event took place.
+    pub timestamp: i64,
+}
+
+/// Triggered when burned tokens are recorded.
+#[event]
+pub struct BurnRecordedEvent {
+    /// Status code
+    pub status_code: u64,
+    /// Status description info.
+    pub status_desc: String,
+    /// [Mint] of [GYC] Token.
+    #[index]
+    pub mint: Pubkey,
+    /// Id of the faucet of [mint].
+    pub faucet_id: u64,
+    /// The authority of the config.
+    #[index]
+    pub authority: Pubkey,
+    /// Amount of token burned.
+    pub amount: u64,
+    /// Total amount of token recorded as burned.
+    pub burned_amount: u64,
+    /// When the event took place.
+    pub timestamp: i64,
+}
+
+/// Triggered when the supply of the mint is audited.
+#[event]
+pub struct AuditEvent {
+    /// Status code
+    pub status_code: u64,
+    /// Status description info.
+    pub status_desc: String,
+    /// [Mint] of [GYC] Token.
+    #[index]
+    pub mint: Pubkey,
+    /// Supply of the mint.
     pub supply: u64,
+    /// Supply the faucets of the mint account for.
+    pub expected_supply: i128,
+    /// Supply minus the expected supply.
+    pub discrepancy: i128,
+    /// Whether the supply matches the expected supply.
+    pub balanced: bool,
     /// When the event took place.
     pub timestamp: i64,
 }
@@ -562,4 +1899,66 @@ pub enum StatusInfo {
     InvalidSysvarClock,
     #[msg("Invalid sysvar rent.")]
     InvalidSysvarRent,
+    #[msg("Max token supply reached, the faucet is finished.")]
+    FaucetFinished,
+    #[msg("Invalid emission schedule.")]
+    InvalidEmissionSchedule,
+    #[msg("Invalid authority.")]
+    InvalidAuthority,
+    #[msg("Invalid pending authority.")]
+    InvalidPendingAuthority,
+    #[msg("Drip is paused.")]
+    Paused,
+    #[msg("Drip is not paused.")]
+    NotPaused,
+    #[msg("Invalid bucket.")]
+    InvalidBucket,
+    #[msg("Token receiver account is not owned by the token program.")]
+    InvalidReceiverOwner,
+    #[msg("Token receiver account is not an account of the token mint.")]
+    InvalidReceiverMint,
+    #[msg("Token receiver account is frozen.")]
+    FrozenReceiverTokenAccount,
+    #[msg("Token receiver account is used by more than one bucket.")]
+    DuplicateReceiverTokenAccount,
+    #[msg("Invalid program data account.")]
+    InvalidProgramData,
+    #[msg("Signer is not the upgrade authority of the program.")]
+    InvalidUpgradeAuthority,
+    #[msg("Invalid token mint decimals.")]
+    InvalidMintDecimals,
+    #[msg("Invalid token freeze authority.")]
+    InvalidFreezeAuthority,
+    #[msg("Nothing accrued to the bucket.")]
+    NothingToClaim,
+    #[msg("Arithmetic overflow.")]
+    ArithmeticOverflow,
+    #[msg("Timestamp or slot out of range.")]
+    TimestampConversion,
+    #[msg("Emission has not started yet.")]
+    EmissionNotStarted,
+    #[msg("Emission has ended.")]
+    EmissionEnded,
+    #[msg("Invalid slot.")]
+    InvalidSlot,
+    #[msg("Division by zero.")]
+    DivisionByZero,
+}
+
+impl From<emission::EmissionError> for StatusInfo {
+    fn from(error: emission::EmissionError) -> StatusInfo {
+        match error {
+            emission::EmissionError::TotalSupplyLimit => StatusInfo::TotalSupplyLimit,
+            emission::EmissionError::FaucetFinished => StatusInfo::FaucetFinished,
+            emission::EmissionError::Paused => StatusInfo::Paused,
+            emission::EmissionError::EmissionNotStarted => StatusInfo::EmissionNotStarted,
+            emission::EmissionError::EmissionEnded => StatusInfo::EmissionEnded,
+            emission::EmissionError::InvalidTimestamp => StatusInfo::InvalidTimestamp,
+            emission::EmissionError::InsufficientInterval => StatusInfo::InsufficientIntervalError,
+            emission::EmissionError::InvalidEmissionSchedule => StatusInfo::InvalidEmissionSchedule,
+            emission::EmissionError::ArithmeticOverflow => StatusInfo::ArithmeticOverflow,
+            emission::EmissionError::TimestampConversion => StatusInfo::TimestampConversion,
+            emission::EmissionError::DivisionByZero => StatusInfo::DivisionByZero,
+        }
+    }
 }
@@ -80,6 +80,32 @@ describe("token-faucet", () => {
   const program = anchor.workspace.TokenFaucet as Program<TokenFaucet>;
   const tokenDecimals = 9;
 
+  // Id of the faucet of the token mint.
+  const faucetId = new anchor.BN(0);
+
+  // Emission schedule: a block every 3 seconds, rewarding 3 GYC at first
+  // and halved every 35 million blocks until the reward drops to zero.
+  const blockGenRate = 3;
+  // A single drip generates at most one hour of blocks.
+  const maxBlocksPerDrip = new anchor.BN(1200);
+  // The caller of drip is rewarded with 0.1% of it, at most 1 GYC.
+  const crankerRewardBps = 10;
+  const maxCrankerReward = new anchor.BN(1_000_000_000);
+  const halving = {
+    halvingInterval: new anchor.BN(35_000_000),
+    initialReward: new anchor.BN(3_000_000_000),
+    halvingEras: 32,
+  };
+  // The segments generated by the program from the halving schedule.
+  const segments = Array.from({ length: 33 }, (_, era) => ({
+    startBlockHeight: new anchor.BN(35_000_000).muln(era),
+    rewardPerBlock: new anchor.BN(3_000_000_000).shrn(era),
+  }));
+
+  // Distribution weights of the buckets: arena, nft mining, liquidity mining,
+  // marketing, ecosystem and GYC staking.
+  const weights = [5, 12, 13, 3, 20, 15].map((weight) => new anchor.BN(weight));
+
   // Several Token Recipient account.
   let receiver_arena: anchor.web3.Keypair;
   let receiver_nft_mining: anchor.web3.Keypair;
@@ -96,15 +122,38 @@ describe("token-faucet", () => {
   let associated_token_account_of_receiver_ecosystem: anchor.web3.PublicKey;
   let associated_token_account_of_receiver_gyc_staking: anchor.web3.PublicKey;
 
+  // The recipient's associated token accounts of the buckets, passed as remaining accounts.
+  const receiverAccounts = () =>
+    [
+      associated_token_account_of_receiver_arena,
+      associated_token_account_of_receiver_nft_mining,
+      associated_token_account_of_receiver_liquidity_mining,
+      associated_token_account_of_receiver_marketing,
+      associated_token_account_of_receiver_ecosystem,
+      associated_token_account_of_receiver_gyc_staking,
+    ].map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }));
+
   // config account of program.
   let config: anchor.web3.PublicKey;
 
+  // Program data account holding the upgrade authority of program.
+  let programData: anchor.web3.PublicKey;
+
+  // emission schedule of config account.
+  let emissionSchedule: anchor.web3.PublicKey;
+
+  // Ledger shared by the faucets of the mint.
+  let mintLedger: anchor.web3.PublicKey;
+
   // GYC token mint.
   let mint: anchor.web3.PublicKey;
   let mintAuthority: anchor.web3.PublicKey;
   let bump: number;
   //let nonce_config: number;
 
+  // Token account of the wallet, receiving the reward for calling drip.
+  let cranker: anchor.web3.PublicKey;
+
   before(async () => {
     console.log(`Before: `);
 
@@ -116,20 +165,56 @@ describe("token-faucet", () => {
     receiver_ecosystem = anchor.web3.Keypair.generate();
     receiver_gyc_staking = anchor.web3.Keypair.generate();
 
+    // Create a new random token mint.
+    mint = await createMint(provider, provider.wallet.publicKey, tokenDecimals);
+
     // Get config account address; it's PDA.
     [config] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from("GameYoo-Token")],
+      [
+        Buffer.from("GameYoo-Token"),
+        mint.toBuffer(),
+        faucetId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    // Get program data address of the upgradeable program.
+    [programData] = await anchor.web3.PublicKey.findProgramAddress(
+      [program.programId.toBuffer()],
+      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    // Get emission schedule address; it's PDA.
+    [emissionSchedule] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("Emission-Schedule"), config.toBuffer()],
+      program.programId
+    );
+
+    // Get mint ledger address; it's PDA.
+    [mintLedger] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("Mint-Ledger"), mint.toBuffer()],
       program.programId
     );
 
     // Get mint authority address; it's PDA.
     [mintAuthority, bump] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from("GYC-Mint-Auth")],
+      [Buffer.from("GYC-Mint-Auth"), mint.toBuffer()],
       program.programId
     );
 
-    // Create a new random token mint.
-    mint = await createMint(provider, mintAuthority, tokenDecimals);
+    // Hand the token mint over to the program.
+    await new Token(
+      provider.connection,
+      mint,
+      TOKEN_PROGRAM_ID,
+      provider.wallet.payer
+    ).setAuthority(
+      mint,
+      mintAuthority,
+      "MintTokens",
+      provider.wallet.publicKey,
+      []
+    );
 
     /*
         Get several associated token account address.
@@ -239,6 +324,12 @@ describe("token-faucet", () => {
       provider.wallet.payer
     );
 
+    cranker = await createTokenAccount(
+      provider,
+      mint,
+      provider.wallet.publicKey
+    );
+
     console.log(`
 config: ${config.toBase58()}
 bump: ${bump}
@@ -260,6 +351,115 @@ associated_token_account_of_receiver_gyc_staking: ${associated_token_account_of_
   });
 
   describe("#Initialize", () => {
+    it("Reject initialization from non-upgrade-authority", async () => {
+      const stranger = anchor.web3.Keypair.generate();
+
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          stranger.publicKey,
+          anchor.web3.LAMPORTS_PER_SOL
+        )
+      );
+
+      await assert.rejects(
+        program.rpc.initialize(
+          faucetId,
+          {
+            clockSource: { unixTimestamp: {} },
+            blockGenRate,
+            maxBlocksPerDrip,
+            graceBlocks: null,
+            crankerRewardBps,
+            maxCrankerReward,
+            startTimestamp: null,
+            endTimestamp: null,
+            startSlot: null,
+            endSlot: null,
+            schedule: { segments: { segments } },
+            weights,
+            checkFreezeAuthority: true,
+          },
+          {
+            accounts: {
+              configAccount: config,
+              emissionSchedule: emissionSchedule,
+              payer: stranger.publicKey,
+              program: program.programId,
+              programData: programData,
+              tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+              mint: mint,
+              mintLedger: mintLedger,
+              mintAuthority: mintAuthority,
+              systemProgram: SystemProgram.programId,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              rent: SYSVAR_RENT_PUBKEY,
+            },
+            remainingAccounts: receiverAccounts(),
+            signers: [stranger],
+          }
+        ),
+        (err: any) =>
+          err.msg === "Signer is not the upgrade authority of the program."
+      );
+    });
+
+    it("Reject weights summing past u64", async () => {
+      // Another faucet of the mint, so that its accounts are still free.
+      const otherFaucetId = new anchor.BN(1);
+      const [otherConfig] = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("GameYoo-Token"),
+          mint.toBuffer(),
+          otherFaucetId.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      const [otherEmissionSchedule] =
+        await anchor.web3.PublicKey.findProgramAddress(
+          [Buffer.from("Emission-Schedule"), otherConfig.toBuffer()],
+          program.programId
+        );
+
+      await assert.rejects(
+        program.rpc.initialize(
+          otherFaucetId,
+          {
+            clockSource: { unixTimestamp: {} },
+            blockGenRate,
+            maxBlocksPerDrip,
+            graceBlocks: null,
+            crankerRewardBps,
+            maxCrankerReward,
+            startTimestamp: null,
+            endTimestamp: null,
+            startSlot: null,
+            endSlot: null,
+            schedule: { halving },
+            weights: [new anchor.BN("18446744073709551615"), new anchor.BN(1)],
+            checkFreezeAuthority: true,
+          },
+          {
+            accounts: {
+              configAccount: otherConfig,
+              emissionSchedule: otherEmissionSchedule,
+              mintLedger: mintLedger,
+              payer: provider.wallet.publicKey,
+              program: program.programId,
+              programData: programData,
+              tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+              mint: mint,
+              mintAuthority: mintAuthority,
+              systemProgram: SystemProgram.programId,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              rent: SYSVAR_RENT_PUBKEY,
+            },
+            remainingAccounts: receiverAccounts().slice(0, 2),
+          }
+        ),
+        (err: any) => err.msg === "Invalid bucket."
+      );
+    });
+
     it("Initialize program state once", async () => {
       // Listen Initialize event of on-chain program.
       let listener = program.addEventListener(
@@ -273,12 +473,8 @@ status_code: ${event.statusCode}
 status_desc: ${event.statusDesc}
 mint: ${event.mint.toBase58()}
 mint_authority: ${event.mintAuthority.toBase58()}
-receiver_arena: ${event.receiverArena.toBase58()}
-receiver_nft_mining: ${event.receiverNftMining.toBase58()}
-receiver_liquidity_mining: ${event.receiverLiquidityMining.toBase58()}
-receiver_marketing: ${event.receiverMarketing.toBase58()}
-receiver_ecosystem: ${event.receiverEcosystem.toBase58()}
-receiver_gyc_staking: ${event.receiver_gyc_staking.toBase58()}
+receivers: ${event.receivers.map((receiver) => receiver.toBase58())}
+weights: ${event.weights}
 current_block_height: ${event.currentBlockHeight}
 last_gen_block_timestamp: ${event.lastGenBlockTimestamp}
 timestamp: ${event.timestamp}\n`
@@ -286,27 +482,44 @@ timestamp: ${event.timestamp}\n`
         }
       );
 
-      // Initialize the state of program by Invoke initialize instruction of on-chain program.
-      const tx = await program.rpc.initialize(bump, {
-        accounts: {
-          configAccount: config,
-          payer: provider.wallet.publicKey,
-          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
-          mint: mint,
-          mintAuthority: mintAuthority,
-          receiverArena: associated_token_account_of_receiver_arena,
-          receiverNftMining: associated_token_account_of_receiver_nft_mining,
-          receiverLiquidityMining:
-            associated_token_account_of_receiver_liquidity_mining,
-          receiverMarketing: associated_token_account_of_receiver_marketing,
-          receiverEcosystem: associated_token_account_of_receiver_ecosystem,
-          receiverGycStaking: associated_token_account_of_receiver_gyc_staking,
-          systemProgram: SystemProgram.programId,
-          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-          rent: SYSVAR_RENT_PUBKEY,
+      // Initialize the state of program by Invoke initialize instruction of on-chain program;
+      // the faucet id defaults to 0.
+      const tx = await program.rpc.initialize(
+        null,
+        {
+          clockSource: { unixTimestamp: {} },
+          blockGenRate,
+          maxBlocksPerDrip,
+          graceBlocks: null,
+          crankerRewardBps,
+          maxCrankerReward,
+          startTimestamp: null,
+          endTimestamp: null,
+          startSlot: null,
+          endSlot: null,
+          schedule: { halving },
+          weights,
+          checkFreezeAuthority: true,
         },
-        signers: [],
-      });
+        {
+          accounts: {
+            configAccount: config,
+            emissionSchedule: emissionSchedule,
+            payer: provider.wallet.publicKey,
+            program: program.programId,
+            programData: programData,
+            tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+            mint: mint,
+            mintLedger: mintLedger,
+            mintAuthority: mintAuthority,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            rent: SYSVAR_RENT_PUBKEY,
+          },
+          remainingAccounts: receiverAccounts(),
+          signers: [],
+        }
+      );
 
       console.log(`Initialize transaction signature: ${tx}`);
 
@@ -315,42 +528,78 @@ timestamp: ${event.timestamp}\n`
       // Verify.
       assert.strictEqual(configAccount.mint.toBase58(), mint.toBase58());
 
+      assert.ok(configAccount.faucetId.eq(faucetId));
+
       assert.strictEqual(
         configAccount.mintAuthority.toBase58(),
         mintAuthority.toBase58()
       );
 
       assert.strictEqual(
-        configAccount.receiverArena.toBase58(),
+        configAccount.buckets[0].receiver.toBase58(),
         associated_token_account_of_receiver_arena.toBase58()
       );
 
       assert.strictEqual(
-        configAccount.receiverNftMining.toBase58(),
+        configAccount.buckets[1].receiver.toBase58(),
         associated_token_account_of_receiver_nft_mining.toBase58()
       );
 
       assert.strictEqual(
-        configAccount.receiverLiquidityMining.toBase58(),
+        configAccount.buckets[2].receiver.toBase58(),
         associated_token_account_of_receiver_liquidity_mining.toBase58()
       );
 
       assert.strictEqual(
-        configAccount.receiverMarketing.toBase58(),
+        configAccount.buckets[3].receiver.toBase58(),
         associated_token_account_of_receiver_marketing.toBase58()
       );
 
       assert.strictEqual(
-        configAccount.receiverEcosystem.toBase58(),
+        configAccount.buckets[4].receiver.toBase58(),
         associated_token_account_of_receiver_ecosystem.toBase58()
       );
 
       assert.strictEqual(
-        configAccount.receiverGycStaking.toBase58(),
+        configAccount.buckets[5].receiver.toBase58(),
         associated_token_account_of_receiver_gyc_staking.toBase58()
       );
 
-      assert.strictEqual(configAccount.bump, bump);
+      assert.strictEqual(configAccount.bucketCount, weights.length);
+
+      assert.ok(configAccount.buckets[0].weight.eq(weights[0]));
+
+      assert.strictEqual(configAccount.mintAuthorityBump, bump);
+
+      assert.strictEqual(configAccount.blockGenRate, blockGenRate);
+
+      assert.ok(configAccount.maxBlocksPerDrip.eq(maxBlocksPerDrip));
+
+      assert.ok(!configAccount.forfeitMissedBlocks);
+
+      assert.ok(
+        configAccount.startTimestamp.eq(configAccount.lastGenBlockTimestamp)
+      );
+
+      assert.ok(configAccount.endTimestamp.isZero());
+
+      assert.deepStrictEqual(configAccount.clockSource, { unixTimestamp: {} });
+
+      assert.strictEqual(configAccount.currentEra, 0);
+
+      assert.ok(configAccount.currentReward.eq(segments[0].rewardPerBlock));
+
+      const emissionScheduleAccount =
+        await program.account.emissionSchedule.fetch(emissionSchedule);
+
+      assert.strictEqual(
+        emissionScheduleAccount.segments.length,
+        segments.length
+      );
+      emissionScheduleAccount.segments.forEach((segment, era) => {
+        assert.ok(segment.startBlockHeight.eq(segments[era].startBlockHeight));
+        assert.ok(segment.rewardPerBlock.eq(segments[era].rewardPerBlock));
+      });
 
       //await program.removeEventListener(listener);
     });
@@ -375,22 +624,16 @@ timestamp: ${event.timestamp}\n`
 status_code: ${event.statusCode}
 status_desc: ${event.statusDesc}
 mint: ${event.mint.toBase58()}
-mint_authority: ${event.mintAuthority.toBase58()}
-receiver_arena: ${event.receiverArena.toBase58()}
-receiver_nft_mining: ${event.receiverNftMining.toBase58()}
-receiver_liquidity_mining: ${event.receiverLiquidityMining.toBase58()}
-receiver_marketing: ${event.receiverMarketing.toBase58()}
-receiver_ecosystem: ${event.receiverEcosystem.toBase58()}
-receiver_gyc_staking: ${event.receiverGycStaking.toBase58()}
 current_block_height: ${event.currentBlockHeight}
 last_gen_block_timestamp: ${event.lastGenBlockTimestamp}
-receiverArenaAmount: ${event.receiverArenaAmount}
-receiverNftMiningAmount: ${event.receiverNftMiningAmount}
-receiverLiquidityMiningAmount: ${event.receiverLiquidityMiningAmount}
-receiverMarketingAmount: ${event.receiverMarketingAmount}
-receiverEcosystemAmount: ${event.receiverEcosystemAmount}
-receiverGycStakingAmount: ${event.receiverGycStakingAmount}
+last_gen_block_slot: ${event.lastGenBlockSlot}
+amounts: ${event.amounts}
+cranker_reward: ${event.crankerReward}
+total_emitted: ${event.totalEmitted}
 intervals: ${event.intervals}
+backlog_block_num: ${event.backlogBlockNum}
+forfeited_block_num: ${event.forfeitedBlockNum}
+total_forfeited_amount: ${event.totalForfeitedAmount}
 supply: ${event.supply}
 timestamp: ${event.timestamp}\n`
         );
@@ -400,16 +643,12 @@ timestamp: ${event.timestamp}\n`
       const tx = await program.rpc.drip({
         accounts: {
           configAccount: config,
+          emissionSchedule: emissionSchedule,
           tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
           mint: mint,
-          mintAuthority: mintInfo.mintAuthority,
-          receiverArena: associated_token_account_of_receiver_arena,
-          receiverNftMining: associated_token_account_of_receiver_nft_mining,
-          receiverLiquidityMining:
-            associated_token_account_of_receiver_liquidity_mining,
-          receiverMarketing: associated_token_account_of_receiver_marketing,
-          receiverEcosystem: associated_token_account_of_receiver_ecosystem,
-          receiverGycStaking: associated_token_account_of_receiver_gyc_staking,
+          mintLedger: mintLedger,
+          mintAuthority: mintAuthority,
+          cranker: cranker,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         },
         signers: [],
@@ -417,6 +656,38 @@ timestamp: ${event.timestamp}\n`
 
       console.log(`drip transaction signature: ${tx}`);
 
+      // Claim the accrual of every bucket, so that it is minted to its recipient.
+      const receivers = receiverAccounts();
+      for (let bucket = 0; bucket < receivers.length; bucket++) {
+        await program.rpc.claimBucket(bucket, {
+          accounts: {
+            configAccount: config,
+            tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+            mint: mint,
+            mintLedger: mintLedger,
+            mintAuthority: mintAuthority,
+            receiver: receivers[bucket].pubkey,
+          },
+        });
+      }
+
+      // Nothing is left to claim.
+      await assert.rejects(
+        program.rpc.claimBucket(0, {
+          accounts: {
+            configAccount: config,
+            tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+            mint: mint,
+            mintLedger: mintLedger,
+            mintAuthority: mintAuthority,
+            receiver: associated_token_account_of_receiver_arena,
+          },
+        })
+      );
+      assert.ok(
+        (await program.account.mintLedger.fetch(mintLedger)).outstanding.isZero()
+      );
+
       // Get and print associated token account's amount of several recipient.
       let _associated_token_account_of_receiver_arena = await getTokenAccount(
         provider,
@@ -488,9 +759,61 @@ timestamp: ${event.timestamp}\n`
         }`
       );
 
+      // Verify that every minted token has been claimed by a recipient or rewarded to the cranker.
+      const distributed = [
+        _associated_token_account_of_receiver_arena,
+        _associated_token_account_of_receiver_nft_mining,
+        _associated_token_account_of_receiver_liquidity_mining,
+        _associated_token_account_of_receiver_marketing,
+        _associated_token_account_of_receiver_ecosystem,
+        _associated_token_account_of_receiver_gyc_staking,
+      ].reduce((sum, account) => sum.add(account.amount), new anchor.BN(0));
+      const _cranker = await getTokenAccount(provider, cranker);
+      assert.ok(_cranker.amount.gtn(0));
+      assert.ok(_cranker.amount.lte(maxCrankerReward));
+      const supply = (await getMintInfo(provider, mint)).supply;
+      assert.ok(distributed.add(_cranker.amount).eq(supply));
+
+      // Verify the lifetime totals recorded by the config.
+      const configAccount = await program.account.configAccount.fetch(config);
+      assert.ok(configAccount.totalEmitted.eq(supply));
+      const bucketTotals = configAccount.buckets
+        .slice(0, configAccount.bucketCount)
+        .reduce((sum, bucket) => sum.add(bucket.totalAccrued), new anchor.BN(0));
+      assert.ok(bucketTotals.eq(distributed));
+
       await program.removeEventListener(listener);
     });
 
+    it("Preview the next drip", async () => {
+      await sleep(3000);
+
+      // The plan is returned as return data, logged by the runtime as base64.
+      const { raw } = await program.simulate.previewDrip({
+        accounts: {
+          configAccount: config,
+          emissionSchedule: emissionSchedule,
+          mint: mint,
+          mintLedger: mintLedger,
+        },
+      });
+      const prefix = `Program return: ${program.programId.toBase58()} `;
+      const returnData = raw.find((log) => log.startsWith(prefix));
+      const plan = program.coder.types.decode(
+        "DripPlan",
+        Buffer.from(returnData.slice(prefix.length), "base64")
+      );
+
+      const configAccount = await program.account.configAccount.fetch(config);
+      assert.ok(plan.genBlockNum.gtn(0));
+      assert.strictEqual(plan.amounts.length, configAccount.bucketCount);
+      assert.ok(
+        plan.currentBlockHeight.eq(
+          configAccount.currentBlockHeight.add(plan.genBlockNum)
+        )
+      );
+    });
+
     it("Drip 100 times", async () => {
       let i = 1;
 
@@ -501,141 +824,578 @@ timestamp: ${event.timestamp}\n`
 status_code: ${event.statusCode}
 status_desc: ${event.statusDesc}
 mint: ${event.mint.toBase58()}
-mint_authority: ${event.mintAuthority.toBase58()}
-receiver_arena: ${event.receiverArena.toBase58()}
-receiver_nft_mining: ${event.receiverNftMining.toBase58()}
-receiver_liquidity_mining: ${event.receiverLiquidityMining.toBase58()}
-receiver_marketing: ${event.receiverMarketing.toBase58()}
-receiver_ecosystem: ${event.receiverEcosystem.toBase58()}
-receiver_gyc_staking: ${event.receiverGycStaking.toBase58()}
 current_block_height: ${event.currentBlockHeight}
 last_gen_block_timestamp: ${event.lastGenBlockTimestamp}
-receiverArenaAmount: ${event.receiverArenaAmount}
-receiverNftMiningAmount: ${event.receiverNftMiningAmount}
-receiverLiquidityMiningAmount: ${event.receiverLiquidityMiningAmount}
-receiverMarketingAmount: ${event.receiverMarketingAmount}
-receiverEcosystemAmount: ${event.receiverEcosystemAmount}
-receiverGycStakingAmount: ${event.receiverGycStakingAmount}
+last_gen_block_slot: ${event.lastGenBlockSlot}
+amounts: ${event.amounts}
+cranker_reward: ${event.crankerReward}
+total_emitted: ${event.totalEmitted}
 intervals: ${event.intervals}
+backlog_block_num: ${event.backlogBlockNum}
+forfeited_block_num: ${event.forfeitedBlockNum}
+total_forfeited_amount: ${event.totalForfeitedAmount}
 supply: ${event.supply}
 timestamp: ${event.timestamp}\n`
         );
       });
 
-      // Minted tokens 100 times in a cycle for several receivers
+      // Sum of the lifetime accruals of the buckets.
+      const totalAccrued = (configAccount) =>
+        configAccount.buckets
+          .slice(0, configAccount.bucketCount)
+          .reduce((sum, bucket) => sum.add(bucket.totalAccrued), new anchor.BN(0));
+
+      // Drip 100 times, each drip generating at least a block and accruing its tokens.
       while (i <= 100) {
-        await sleep(3000);
+        // Wait for more than a block, so that one is pending.
+        await sleep(blockGenRate * 1000 + 1000);
+
+        const before = await program.account.configAccount.fetch(config);
+        const crankerBefore = (await getTokenAccount(provider, cranker)).amount;
+
+        // Accrue tokens to the buckets by Invoke drip instruction of on-chain program.
+        const tx = await program.rpc.drip({
+          accounts: {
+            configAccount: config,
+            emissionSchedule: emissionSchedule,
+            tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+            mint: mint,
+            mintLedger: mintLedger,
+            mintAuthority: mintAuthority,
+            cranker: cranker,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [],
+        });
+
+        console.log(`Drip transaction signature: ${tx}, index: ${i}`);
+
+        // The block height moves on and the buckets accrue the tokens of the new blocks,
+        // which with the cranker reward make up the emitted total.
+        const after = await program.account.configAccount.fetch(config);
+        const crankerAfter = (await getTokenAccount(provider, cranker)).amount;
+        assert.ok(after.currentBlockHeight.gt(before.currentBlockHeight));
+        const accrued = totalAccrued(after).sub(totalAccrued(before));
+        assert.ok(accrued.gtn(0));
+        assert.ok(
+          after.totalEmitted
+            .sub(before.totalEmitted)
+            .eq(accrued.add(crankerAfter.sub(crankerBefore)))
+        );
 
-        try {
-          const mintInfo = await getMintInfo(provider, mint);
+        i++;
+      }
 
-          assert.strictEqual(
-            mintInfo.mintAuthority.toBase58(),
-            mintAuthority.toBase58()
-          );
+      // Claim the accrual of every bucket, which is minted to its recipient.
+      const configAccount = await program.account.configAccount.fetch(config);
+      const receivers = receiverAccounts();
+      for (let bucket = 0; bucket < receivers.length; bucket++) {
+        const balanceBefore = (
+          await getTokenAccount(provider, receivers[bucket].pubkey)
+        ).amount;
+        await program.rpc.claimBucket(bucket, {
+          accounts: {
+            configAccount: config,
+            tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+            mint: mint,
+            mintLedger: mintLedger,
+            mintAuthority: mintAuthority,
+            receiver: receivers[bucket].pubkey,
+          },
+        });
+        const balanceAfter = (
+          await getTokenAccount(provider, receivers[bucket].pubkey)
+        ).amount;
+        assert.ok(
+          balanceAfter
+            .sub(balanceBefore)
+            .eq(configAccount.buckets[bucket].accrued)
+        );
+      }
 
-          // Mint token to several recipient by Invoke drip instruction of on-chain program.
-          const tx = await program.rpc.drip({
-            accounts: {
-              configAccount: config,
-              tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
-              mint: mint,
-              mintAuthority: mintInfo.mintAuthority,
-              receiverArena: associated_token_account_of_receiver_arena,
-              receiverNftMining:
-                associated_token_account_of_receiver_nft_mining,
-              receiverLiquidityMining:
-                associated_token_account_of_receiver_liquidity_mining,
-              receiverMarketing: associated_token_account_of_receiver_marketing,
-              receiverEcosystem: associated_token_account_of_receiver_ecosystem,
-              receiverGycStaking:
-                associated_token_account_of_receiver_gyc_staking,
-              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-            },
-            signers: [],
-          });
+      //await program.removeEventListener(listener);
+    });
+  });
 
-          console.log(`Drip transaction signature: ${tx}, index: ${i}`);
+  describe("#Authority", () => {
+    it("Reject proposal from non-authority", async () => {
+      const stranger = anchor.web3.Keypair.generate();
+
+      await assert.rejects(
+        program.rpc.proposeAuthority(stranger.publicKey, {
+          accounts: {
+            configAccount: config,
+            authority: stranger.publicKey,
+          },
+          signers: [stranger],
+        })
+      );
+    });
 
-          // Get and print associated token account's amount of several recipient.
-          let _associated_token_account_of_receiver_arena =
-            await getTokenAccount(
-              provider,
-              associated_token_account_of_receiver_arena
-            );
-          console.log(
-            `arena associated_token: ${associated_token_account_of_receiver_arena.toBase58()}, amount: ${
-              _associated_token_account_of_receiver_arena.amount
-            }`
-          );
+    it("Transfer authority in two steps", async () => {
+      const newAuthority = anchor.web3.Keypair.generate();
 
-          let _associated_token_account_of_receiver_nft_mining =
-            await getTokenAccount(
-              provider,
-              associated_token_account_of_receiver_nft_mining
-            );
+      await program.rpc.proposeAuthority(newAuthority.publicKey, {
+        accounts: {
+          configAccount: config,
+          authority: provider.wallet.publicKey,
+        },
+      });
 
-          console.log(
-            `nft_mining associated_token: ${associated_token_account_of_receiver_nft_mining.toBase58()}, amount: ${
-              _associated_token_account_of_receiver_nft_mining.amount
-            }`
-          );
+      let configAccount = await program.account.configAccount.fetch(config);
+      assert.strictEqual(
+        configAccount.authority.toBase58(),
+        provider.wallet.publicKey.toBase58()
+      );
+      assert.strictEqual(
+        configAccount.pendingAuthority.toBase58(),
+        newAuthority.publicKey.toBase58()
+      );
 
-          let _associated_token_account_of_receiver_liquidity_mining =
-            await getTokenAccount(
-              provider,
-              associated_token_account_of_receiver_liquidity_mining
-            );
+      // Only the proposed authority can accept.
+      await assert.rejects(
+        program.rpc.acceptAuthority({
+          accounts: {
+            configAccount: config,
+            pendingAuthority: provider.wallet.publicKey,
+          },
+        })
+      );
 
-          console.log(
-            `liquidity_mining associated_token: ${associated_token_account_of_receiver_liquidity_mining.toBase58()}, amount: ${
-              _associated_token_account_of_receiver_liquidity_mining.amount
-            }`
-          );
+      await program.rpc.acceptAuthority({
+        accounts: {
+          configAccount: config,
+          pendingAuthority: newAuthority.publicKey,
+        },
+        signers: [newAuthority],
+      });
 
-          let _associated_token_account_of_receiver_marketing =
-            await getTokenAccount(
-              provider,
-              associated_token_account_of_receiver_marketing
-            );
+      configAccount = await program.account.configAccount.fetch(config);
+      assert.strictEqual(
+        configAccount.authority.toBase58(),
+        newAuthority.publicKey.toBase58()
+      );
+      assert.strictEqual(
+        configAccount.pendingAuthority.toBase58(),
+        anchor.web3.PublicKey.default.toBase58()
+      );
 
-          console.log(
-            `marketing_and_sales associated_token: ${associated_token_account_of_receiver_marketing.toBase58()}, amount: ${
-              _associated_token_account_of_receiver_marketing.amount
-            }`
-          );
+      // Hand the authority back to the provider wallet.
+      await program.rpc.proposeAuthority(provider.wallet.publicKey, {
+        accounts: {
+          configAccount: config,
+          authority: newAuthority.publicKey,
+        },
+        signers: [newAuthority],
+      });
 
-          let _associated_token_account_of_receiver_ecosystem =
-            await getTokenAccount(
-              provider,
-              associated_token_account_of_receiver_ecosystem
-            );
+      await program.rpc.acceptAuthority({
+        accounts: {
+          configAccount: config,
+          pendingAuthority: provider.wallet.publicKey,
+        },
+      });
+    });
+  });
 
-          console.log(
-            `ecosystem associated_token: ${associated_token_account_of_receiver_ecosystem.toBase58()}, amount: ${
-              _associated_token_account_of_receiver_ecosystem.amount
-            }`
-          );
+  describe("#Pause", () => {
+    it("Pause and unpause drip", async () => {
+      await program.rpc.pause({
+        accounts: {
+          configAccount: config,
+          authority: provider.wallet.publicKey,
+        },
+      });
 
-          let _associated_token_account_of_receiver_gyc_staking =
-            await getTokenAccount(
-              provider,
-              associated_token_account_of_receiver_gyc_staking
-            );
+      let configAccount = await program.account.configAccount.fetch(config);
+      assert.ok(configAccount.paused);
+      const lastGenBlockTimestamp = configAccount.lastGenBlockTimestamp;
+      const lastGenBlockSlot = configAccount.lastGenBlockSlot;
+
+      await sleep(6000);
+
+      // Drip fails while paused.
+      await assert.rejects(
+        program.rpc.drip({
+          accounts: {
+            configAccount: config,
+            emissionSchedule: emissionSchedule,
+            tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+            mint: mint,
+            mintLedger: mintLedger,
+            mintAuthority: mintAuthority,
+            cranker: cranker,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+        })
+      );
 
-          console.log(
-            `gyc_staking associated_token: ${associated_token_account_of_receiver_gyc_staking.toBase58()}, amount: ${
-              _associated_token_account_of_receiver_gyc_staking.amount
-            }`
-          );
-        } catch (error) {
-          console.log(error);
-        }
+      await program.rpc.unpause({
+        accounts: {
+          configAccount: config,
+          authority: provider.wallet.publicKey,
+        },
+      });
 
-        i++;
+      // The paused time is forfeited.
+      configAccount = await program.account.configAccount.fetch(config);
+      assert.ok(!configAccount.paused);
+      assert.ok(configAccount.lastGenBlockTimestamp.gt(lastGenBlockTimestamp));
+
+      // With the unix timestamp clock source, the slot of the last drip is left as is.
+      assert.ok(configAccount.lastGenBlockSlot.eq(lastGenBlockSlot));
+    });
+  });
+
+  describe("#Receiver", () => {
+    it("Rotate the recipient of arena", async () => {
+      const newReceiverArena = await createTokenAccount(
+        provider,
+        mint,
+        anchor.web3.Keypair.generate().publicKey
+      );
+
+      await program.rpc.setReceiver(0, {
+        accounts: {
+          configAccount: config,
+          authority: provider.wallet.publicKey,
+          newReceiver: newReceiverArena,
+        },
+      });
+
+      let configAccount = await program.account.configAccount.fetch(config);
+      assert.strictEqual(
+        configAccount.buckets[0].receiver.toBase58(),
+        newReceiverArena.toBase58()
+      );
+
+      // Restore the recipient for the other test cases.
+      await program.rpc.setReceiver(0, {
+        accounts: {
+          configAccount: config,
+          authority: provider.wallet.publicKey,
+          newReceiver: associated_token_account_of_receiver_arena,
+        },
+      });
+
+      configAccount = await program.account.configAccount.fetch(config);
+      assert.strictEqual(
+        configAccount.buckets[0].receiver.toBase58(),
+        associated_token_account_of_receiver_arena.toBase58()
+      );
+    });
+
+    it("Reject a token account of another mint", async () => {
+      const otherMint = await createMint(provider, mintAuthority, tokenDecimals);
+      const otherReceiver = await createTokenAccount(
+        provider,
+        otherMint,
+        provider.wallet.publicKey
+      );
+
+      await assert.rejects(
+        program.rpc.setReceiver(0, {
+          accounts: {
+            configAccount: config,
+            authority: provider.wallet.publicKey,
+            newReceiver: otherReceiver,
+          },
+        })
+      );
+    });
+
+    it("Reject a token account used by another bucket", async () => {
+      await assert.rejects(
+        program.rpc.setReceiver(0, {
+          accounts: {
+            configAccount: config,
+            authority: provider.wallet.publicKey,
+            newReceiver: associated_token_account_of_receiver_nft_mining,
+          },
+        })
+      );
+    });
+  });
+
+  describe("#Audit", () => {
+    // Audit the supply of the mint, reading the AuditEvent from a simulation.
+    const audit = async () => {
+      const { events } = await program.simulate.audit({
+        accounts: {
+          mint: mint,
+          mintLedger: mintLedger,
+        },
+      });
+      return events.find((event) => event.name === "AuditEvent").data;
+    };
+
+    it("Reconcile the supply with recorded burns", async () => {
+      // Every token minted so far was emitted by the faucet.
+      let report = await audit();
+      assert.ok(report.balanced);
+
+      // A burn by a holder shows up as a discrepancy until it is recorded.
+      await new Token(
+        provider.connection,
+        mint,
+        TOKEN_PROGRAM_ID,
+        provider.wallet.payer
+      ).burn(cranker, provider.wallet.publicKey, [], 1);
+
+      report = await audit();
+      assert.ok(!report.balanced);
+      assert.ok(report.discrepancy.eqn(-1));
+
+      await program.rpc.recordBurn(new anchor.BN(1), {
+        accounts: {
+          configAccount: config,
+          authority: provider.wallet.publicKey,
+          mintLedger: mintLedger,
+        },
+      });
+
+      report = await audit();
+      assert.ok(report.balanced);
+    });
+  });
+
+  describe("#Shared mint", () => {
+    const maxTotalSupply = new anchor.BN(210_000_000).mul(
+      new anchor.BN(1_000_000_000)
+    );
+
+    // A new mint with `preMint` tokens held by the wallet, handed over to the program.
+    const createSharedMint = async (preMint) => {
+      const sharedMint = await createMint(
+        provider,
+        provider.wallet.publicKey,
+        tokenDecimals
+      );
+      const [sharedLedger] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("Mint-Ledger"), sharedMint.toBuffer()],
+        program.programId
+      );
+      const [sharedMintAuthority] =
+        await anchor.web3.PublicKey.findProgramAddress(
+          [Buffer.from("GYC-Mint-Auth"), sharedMint.toBuffer()],
+          program.programId
+        );
+      const token = new Token(
+        provider.connection,
+        sharedMint,
+        TOKEN_PROGRAM_ID,
+        provider.wallet.payer
+      );
+      const holder = await token.createAccount(provider.wallet.publicKey);
+      if (preMint > 0) {
+        await token.mintTo(holder, provider.wallet.publicKey, [], preMint);
       }
+      await token.setAuthority(
+        sharedMint,
+        sharedMintAuthority,
+        "MintTokens",
+        provider.wallet.publicKey,
+        []
+      );
 
-      //await program.removeEventListener(listener);
+      return {
+        mint: sharedMint,
+        ledger: sharedLedger,
+        mintAuthority: sharedMintAuthority,
+        token: token,
+        holder: holder,
+      };
+    };
+
+    // Initialize the faucet `id` of the mint, generating a block every second
+    // and accruing `rewardPerBlock` to a single bucket.
+    const initializeFaucet = async (shared, id, rewardPerBlock) => {
+      const [faucetConfig] = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("GameYoo-Token"),
+          shared.mint.toBuffer(),
+          new anchor.BN(id).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      const [faucetSchedule] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("Emission-Schedule"), faucetConfig.toBuffer()],
+        program.programId
+      );
+      const receiver = await createTokenAccount(
+        provider,
+        shared.mint,
+        provider.wallet.publicKey
+      );
+
+      await program.rpc.initialize(
+        new anchor.BN(id),
+        {
+          clockSource: { unixTimestamp: {} },
+          blockGenRate: 1,
+          maxBlocksPerDrip,
+          graceBlocks: null,
+          crankerRewardBps: 0,
+          maxCrankerReward: new anchor.BN(0),
+          startTimestamp: null,
+          endTimestamp: null,
+          startSlot: null,
+          endSlot: null,
+          schedule: {
+            segments: {
+              segments: [
+                {
+                  startBlockHeight: new anchor.BN(0),
+                  rewardPerBlock: rewardPerBlock,
+                },
+              ],
+            },
+          },
+          weights: [new anchor.BN(1)],
+          checkFreezeAuthority: true,
+        },
+        {
+          accounts: {
+            configAccount: faucetConfig,
+            emissionSchedule: faucetSchedule,
+            mintLedger: shared.ledger,
+            payer: provider.wallet.publicKey,
+            program: program.programId,
+            programData: programData,
+            tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+            mint: shared.mint,
+            mintAuthority: shared.mintAuthority,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            rent: SYSVAR_RENT_PUBKEY,
+          },
+          remainingAccounts: [
+            { pubkey: receiver, isWritable: true, isSigner: false },
+          ],
+        }
+      );
+
+      return {
+        config: faucetConfig,
+        schedule: faucetSchedule,
+        receiver: receiver,
+      };
+    };
+
+    const drip = (shared, faucet) =>
+      program.rpc.drip({
+        accounts: {
+          configAccount: faucet.config,
+          emissionSchedule: faucet.schedule,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+          mint: shared.mint,
+          mintLedger: shared.ledger,
+          mintAuthority: shared.mintAuthority,
+          cranker: faucet.receiver,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        },
+      });
+
+    const claim = (shared, faucet) =>
+      program.rpc.claimBucket(0, {
+        accounts: {
+          configAccount: faucet.config,
+          tokenProgram: TokenInstructions.TOKEN_PROGRAM_ID,
+          mint: shared.mint,
+          mintLedger: shared.ledger,
+          mintAuthority: shared.mintAuthority,
+          receiver: faucet.receiver,
+        },
+      });
+
+    // Audit the supply of the mint, reading the AuditEvent from a simulation.
+    const audit = async (shared) => {
+      const { events } = await program.simulate.audit({
+        accounts: {
+          mint: shared.mint,
+          mintLedger: shared.ledger,
+        },
+      });
+      return events.find((event) => event.name === "AuditEvent").data;
+    };
+
+    it("Cap the supply across the faucets of a mint", async () => {
+      const shared = await createSharedMint(0);
+
+      // Two faucets of the mint, each emitting the whole max supply in its first block.
+      const faucets = [
+        await initializeFaucet(shared, 0, maxTotalSupply),
+        await initializeFaucet(shared, 1, maxTotalSupply),
+      ];
+      const supplyExceeded = (err: any) =>
+        err.msg === "Max token supply exceeded.";
+
+      await sleep(2000);
+
+      // The first faucet accrues the whole max supply, leaving nothing to the second.
+      await drip(shared, faucets[0]);
+      assert.ok(
+        (await program.account.mintLedger.fetch(shared.ledger)).outstanding.eq(
+          maxTotalSupply
+        )
+      );
+      await assert.rejects(drip(shared, faucets[1]), supplyExceeded);
+
+      // Once claimed, the accrual is in the supply of the mint, which stays capped.
+      await claim(shared, faucets[0]);
+      assert.ok(
+        (await getMintInfo(provider, shared.mint)).supply.eq(maxTotalSupply)
+      );
+      await assert.rejects(drip(shared, faucets[1]), supplyExceeded);
+    });
+
+    it("Reconcile the supply of a mint with two faucets", async () => {
+      const preMint = 1_000;
+      const shared = await createSharedMint(preMint);
+      const reward = new anchor.BN(1_000_000_000);
+
+      // The second faucet is initialized while the first one has unclaimed accruals,
+      // which do not count as pre-mint supply.
+      const first = await initializeFaucet(shared, 0, reward);
+      await sleep(2000);
+      await drip(shared, first);
+      const second = await initializeFaucet(shared, 1, reward);
+      await sleep(2000);
+      await drip(shared, second);
+      await claim(shared, first);
+
+      // Every token minted so far was either minted before or emitted by a faucet.
+      let report = await audit(shared);
+      assert.ok(report.balanced);
+      const ledger = await program.account.mintLedger.fetch(shared.ledger);
+      assert.ok(ledger.preMintSupply.eqn(preMint));
+      const firstAccount = await program.account.configAccount.fetch(
+        first.config
+      );
+      const secondAccount = await program.account.configAccount.fetch(
+        second.config
+      );
+      assert.ok(
+        ledger.totalEmitted.eq(
+          firstAccount.totalEmitted.add(secondAccount.totalEmitted)
+        )
+      );
+      assert.ok(ledger.outstanding.eq(secondAccount.buckets[0].accrued));
+
+      // A burn recorded by the authority of either faucet balances the mint.
+      await shared.token.burn(shared.holder, provider.wallet.publicKey, [], 1);
+      report = await audit(shared);
+      assert.ok(report.discrepancy.eqn(-1));
+
+      await program.rpc.recordBurn(new anchor.BN(1), {
+        accounts: {
+          configAccount: second.config,
+          authority: provider.wallet.publicKey,
+          mintLedger: shared.ledger,
+        },
+      });
+      report = await audit(shared);
+      assert.ok(report.balanced);
     });
   });
 });